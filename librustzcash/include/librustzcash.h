@@ -130,10 +130,44 @@ extern "C" {
         unsigned char *result
     );
 
+    /// This function constructs a binding signature from an explicit
+    /// binding signing key `bsk` (the sum of the Spend `rcv` values
+    /// minus the sum of the Output `rcv` values). It returns false if
+    /// `bsk` is not a canonical scalar.
+    bool librustzcash_sapling_binding_sig_from_bsk(
+        const unsigned char *bsk,
+        const unsigned char *sighash,
+        unsigned char *result
+    );
+
     /// Frees a Sapling proving context returned from
     /// `librustzcash_sapling_proving_ctx_init`.
     void librustzcash_sapling_proving_ctx_free(void *);
 
+    /// Sets the value commitment randomness `rcv` to use for the next
+    /// Spend or Output proof created with this context, instead of
+    /// sampling it. Returns false if `rcv` is not a canonical scalar.
+    bool librustzcash_sapling_proving_ctx_set_rcv(
+        void *ctx,
+        const unsigned char *rcv
+    );
+
+    /// Writes the value commitment randomness used by the most recent
+    /// Spend or Output proof created with this context. Returns false
+    /// if no proof has been created yet.
+    bool librustzcash_sapling_proving_ctx_get_rcv(
+        const void *ctx,
+        unsigned char *result
+    );
+
+    /// Writes the binding signing key `bsk` accumulated in this context:
+    /// the sum of the Spend `rcv` values minus the sum of the Output
+    /// `rcv` values.
+    void librustzcash_sapling_proving_ctx_get_bsk(
+        const void *ctx,
+        unsigned char *result
+    );
+
     /// Creates a Sapling verification context. Please free this
     /// when you're done.
     void * librustzcash_sapling_verification_ctx_init();
@@ -151,6 +185,20 @@ extern "C" {
         const unsigned char *sighashValue
     );
 
+    /// Check only the zk-SNARK proof of a Sapling Spend description,
+    /// against the public inputs derived from `cv`, `anchor`,
+    /// `nullifier` and `rk`. The spend authorization signature is
+    /// not checked, and no verification context is needed.
+    /// Returns false if any input is invalid or the proof does not
+    /// verify. Requires the parameters to be loaded.
+    bool librustzcash_sapling_check_spend_proof(
+        const unsigned char *cv,
+        const unsigned char *anchor,
+        const unsigned char *nullifier,
+        const unsigned char *rk,
+        const unsigned char *zkproof
+    );
+
     /// Check the validity of a Sapling Output description,
     /// accumulating the value commitment into the context.
     bool librustzcash_sapling_check_output(
@@ -233,8 +281,9 @@ extern "C" {
         unsigned char *result
     );
 
-    /// Sprout JoinSplit proof generation.
-    void librustzcash_sprout_prove(
+    /// Sprout JoinSplit proof generation. Returns false if either
+    /// input's authentication path is malformed.
+    bool librustzcash_sprout_prove(
         unsigned char *proof_out,
 
         const unsigned char *phi,
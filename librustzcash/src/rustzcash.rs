@@ -5,6 +5,7 @@ extern crate libc;
 extern crate pairing;
 extern crate rand;
 extern crate sapling_crypto;
+extern crate zcash_primitives;
 extern crate zip32;
 
 mod hashreader;
@@ -19,27 +20,25 @@ use pairing::{
 
 use sapling_crypto::{
     circuit::multipack,
-    constants::CRH_IVK_PERSONALIZATION,
     jubjub::{
         edwards,
         fs::{Fs, FsRepr},
         FixedGenerators, JubjubBls12, JubjubEngine, JubjubParams, PrimeOrder, ToUniform, Unknown,
     },
+    merkle_tree::{CommitmentTreeWitness, SAPLING_COMMITMENT_TREE_DEPTH},
     pedersen_hash::{pedersen_hash, Personalization},
     redjubjub::{self, Signature},
 };
 
 use sapling_crypto::circuit::sprout::{self, TREE_DEPTH as SPROUT_TREE_DEPTH};
 // TODO: make these consistent
-const SAPLING_TREE_DEPTH: usize = 32;
+const SAPLING_TREE_DEPTH: usize = SAPLING_COMMITMENT_TREE_DEPTH;
 
 use bellman::groth16::{
     create_random_proof, prepare_verifying_key, verify_proof, Parameters, PreparedVerifyingKey,
     Proof, VerifyingKey,
 };
 
-use blake2_rfc::blake2s::Blake2s;
-
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use rand::{OsRng, Rand, Rng};
@@ -61,9 +60,11 @@ use std::ffi::OsString;
 #[cfg(target_os = "windows")]
 use std::os::windows::ffi::OsStringExt;
 
-use sapling_crypto::primitives::{ProofGenerationKey, ValueCommitment, ViewingKey};
+use sapling_crypto::primitives::{
+    IncomingViewingKey, ProofGenerationKey, SaplingNoteValue, ValueCommitment, ViewingKey,
+};
 
-pub mod equihash;
+use zcash_primitives::equihash;
 
 #[cfg(test)]
 mod tests;
@@ -388,17 +389,9 @@ pub extern "system" fn librustzcash_crh_ivk(
     let ak = unsafe { &*ak };
     let nk = unsafe { &*nk };
 
-    let mut h = Blake2s::with_params(32, &[], &[], CRH_IVK_PERSONALIZATION);
-    h.update(ak);
-    h.update(nk);
-    let mut h = h.finalize().as_ref().to_vec();
-
-    // Drop the last five bits, so it can be interpreted as a scalar.
-    h[31] &= 0b0000_0111;
-
     let result = unsafe { &mut *result };
 
-    result.copy_from_slice(&h);
+    *result = sapling_crypto::primitives::prf::crh_ivk(ak, nk);
 }
 
 #[no_mangle]
@@ -413,14 +406,15 @@ pub extern "system" fn librustzcash_ivk_to_pkd(
     diversifier: *const [c_uchar; 11],
     result: *mut [c_uchar; 32],
 ) -> bool {
-    let ivk = read_fs(unsafe { &*ivk });
+    let ivk = match IncomingViewingKey::<Bls12>::read(&(unsafe { &*ivk })[..]) {
+        Ok(ivk) => ivk,
+        Err(_) => return false,
+    };
     let diversifier = sapling_crypto::primitives::Diversifier(unsafe { *diversifier });
-    if let Some(g_d) = diversifier.g_d::<Bls12>(&JUBJUB) {
-        let pk_d = g_d.mul(ivk, &JUBJUB);
-
+    if let Some(addr) = ivk.to_payment_address(diversifier, &JUBJUB) {
         let result = unsafe { &mut *result };
 
-        pk_d.write(&mut result[..]).expect("length is 32 bytes");
+        addr.pk_d.write(&mut result[..]).expect("length is 32 bytes");
 
         true
     } else {
@@ -494,8 +488,14 @@ fn priv_get_note(
         Err(_) => return Err(()),
     };
 
+    // The value cannot exceed the total supply
+    let value = match SaplingNoteValue::from_u64(value) {
+        Some(value) => value,
+        None => return Err(()),
+    };
+
     let note = sapling_crypto::primitives::Note {
-        value,
+        value: value.into(),
         g_d,
         pk_d,
         r,
@@ -744,6 +744,71 @@ pub extern "system" fn librustzcash_sapling_check_spend(
         return false;
     }
 
+    verify_spend_proof(&cv, anchor, nullifier, &rk, unsafe { &*zkproof })
+}
+
+/// Verifies the proof of a Sapling Spend description against the public
+/// inputs derived from its other fields, without checking the spend
+/// authorization signature or using a verification context.
+#[no_mangle]
+pub extern "system" fn librustzcash_sapling_check_spend_proof(
+    cv: *const [c_uchar; 32],
+    anchor: *const [c_uchar; 32],
+    nullifier: *const [c_uchar; 32],
+    rk: *const [c_uchar; 32],
+    zkproof: *const [c_uchar; GROTH_PROOF_SIZE],
+) -> bool {
+    // Deserialize the value commitment
+    let cv = match edwards::Point::<Bls12, Unknown>::read(&(unsafe { &*cv })[..], &JUBJUB) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if is_small_order(&cv) {
+        return false;
+    }
+
+    // Deserialize the anchor, which should be an element
+    // of Fr.
+    let anchor = match Fr::from_repr(read_le(&(unsafe { &*anchor })[..])) {
+        Ok(a) => a,
+        Err(_) => return false,
+    };
+
+    // Deserialize rk
+    let rk = match redjubjub::PublicKey::<Bls12>::read(&(unsafe { &*rk })[..], &JUBJUB) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if is_small_order(&rk.0) {
+        return false;
+    }
+
+    verify_spend_proof(
+        &cv,
+        anchor,
+        &unsafe { &*nullifier }[..],
+        &rk,
+        unsafe { &*zkproof },
+    )
+}
+
+// Private utility function to verify a Spend proof against the public
+// inputs of the circuit
+fn verify_spend_proof(
+    cv: &edwards::Point<Bls12, Unknown>,
+    anchor: Fr,
+    nullifier: &[u8],
+    rk: &redjubjub::PublicKey<Bls12>,
+    zkproof: &[u8; GROTH_PROOF_SIZE],
+) -> bool {
+    // The parameters might not have been loaded yet
+    let vk = match unsafe { SAPLING_SPEND_VK.as_ref() } {
+        Some(vk) => vk,
+        None => return false,
+    };
+
     // Construct public input for circuit
     let mut public_input = [Fr::zero(); 7];
     {
@@ -770,17 +835,13 @@ pub extern "system" fn librustzcash_sapling_check_spend(
     }
 
     // Deserialize the proof
-    let zkproof = match Proof::<Bls12>::read(&(unsafe { &*zkproof })[..]) {
+    let zkproof = match Proof::<Bls12>::read(&zkproof[..]) {
         Ok(p) => p,
         Err(_) => return false,
     };
 
     // Verify the proof
-    match verify_proof(
-        unsafe { SAPLING_SPEND_VK.as_ref() }.unwrap(),
-        &zkproof,
-        &public_input[..],
-    ) {
+    match verify_proof(vk, &zkproof, &public_input[..]) {
         // No error, and proof verification successful
         Ok(true) => true,
 
@@ -975,7 +1036,7 @@ pub extern "system" fn librustzcash_sprout_prove(
     // Public value
     vpub_old: uint64_t,
     vpub_new: uint64_t,
-) {
+) -> bool {
     let phi = unsafe { *phi };
     let rt = unsafe { *rt };
     let h_sig = unsafe { *h_sig };
@@ -1001,13 +1062,17 @@ pub extern "system" fn librustzcash_sprout_prove(
             let a_sk = Some(sprout::SpendingKey(sk));
 
             // skip the first byte
-            assert_eq!(auth[0], SPROUT_TREE_DEPTH as u8);
+            if auth[0] != SPROUT_TREE_DEPTH as u8 {
+                return false;
+            }
             auth = &auth[1..];
 
             let mut auth_path = [None; SPROUT_TREE_DEPTH];
             for i in (0..SPROUT_TREE_DEPTH).rev() {
                 // skip length of inner vector
-                assert_eq!(auth[0], 32);
+                if auth[0] != 32 {
+                    return false;
+                }
                 auth = &auth[1..];
 
                 let mut sibling = [0u8; 32];
@@ -1017,9 +1082,10 @@ pub extern "system" fn librustzcash_sprout_prove(
                 auth_path[i] = Some((sibling, false));
             }
 
-            let mut position = auth
-                .read_u64::<LittleEndian>()
-                .expect("should have had index at the end");
+            let mut position = match auth.read_u64::<LittleEndian>() {
+                Ok(pos) => pos,
+                Err(_) => return false,
+            };
 
             for i in 0..SPROUT_TREE_DEPTH {
                 auth_path[i].as_mut().map(|p| p.1 = (position & 1) == 1);
@@ -1034,10 +1100,15 @@ pub extern "system" fn librustzcash_sprout_prove(
                 r: r,
                 auth_path: auth_path,
             });
+
+            true
         };
 
-        handle_input(in_sk1, in_value1, in_rho1, in_r1, &in_auth1[..]);
-        handle_input(in_sk2, in_value2, in_rho2, in_r2, &in_auth2[..]);
+        if !handle_input(in_sk1, in_value1, in_rho1, in_r1, &in_auth1[..])
+            || !handle_input(in_sk2, in_value2, in_rho2, in_r2, &in_auth2[..])
+        {
+            return false;
+        }
     }
 
     let mut outputs = Vec::with_capacity(2);
@@ -1086,6 +1157,8 @@ pub extern "system" fn librustzcash_sprout_prove(
     proof
         .write(&mut (unsafe { &mut *proof_out })[..])
         .expect("should be able to serialize a proof");
+
+    true
 }
 
 #[no_mangle]
@@ -1140,6 +1213,35 @@ pub extern "system" fn librustzcash_sprout_verify(
 pub struct SaplingProvingContext {
     bsk: Fs,
     bvk: edwards::Point<Bls12, Unknown>,
+    /// Value commitment randomness supplied by the caller for the next proof.
+    next_rcv: Option<Fs>,
+    /// Value commitment randomness used by the most recent proof.
+    last_rcv: Option<Fs>,
+}
+
+impl SaplingProvingContext {
+    // Returns the value commitment randomness for the next proof, without
+    // consuming it.
+    fn rcv<R: Rng>(&self, rng: &mut R) -> Fs {
+        match self.next_rcv {
+            Some(rcv) => rcv,
+            None => Fs::rand(rng),
+        }
+    }
+
+    // Records `rcv` as used by a successful Spend (or Output, if `output` is
+    // true), and accumulates it into `bsk`.
+    fn use_rcv(&mut self, rcv: Fs, output: bool) {
+        self.next_rcv = None;
+        self.last_rcv = Some(rcv);
+
+        if output {
+            // Outputs subtract from the total.
+            self.bsk.sub_assign(&rcv);
+        } else {
+            self.bsk.add_assign(&rcv);
+        }
+    }
 }
 
 #[no_mangle]
@@ -1180,6 +1282,11 @@ pub extern "system" fn librustzcash_sapling_output_proof(
         diversifier: diversifier,
     };
 
+    // The note value cannot exceed the total supply
+    if SaplingNoteValue::from_u64(value).is_none() {
+        return false;
+    }
+
     // Initialize secure RNG
     let mut rng = OsRng::new().expect("should be able to construct RNG");
 
@@ -1189,20 +1296,9 @@ pub extern "system" fn librustzcash_sapling_output_proof(
         Err(_) => return false,
     };
 
-    // We construct ephemeral randomness for the value commitment. This
-    // randomness is not given back to the caller, but the synthetic
-    // blinding factor `bsk` is accumulated in the context.
-    let rcv = Fs::rand(&mut rng);
-
-    // Accumulate the value commitment randomness in the context
-    {
-        let mut tmp = rcv.clone();
-        tmp.negate(); // Outputs subtract from the total.
-        tmp.add_assign(&unsafe { &*ctx }.bsk);
-
-        // Update the context
-        unsafe { &mut *ctx }.bsk = tmp;
-    }
+    // We construct ephemeral randomness for the value commitment, unless the
+    // caller supplied it
+    let rcv = unsafe { &*ctx }.rcv(&mut rng);
 
     // Construct the value commitment for the proof instance
     let value_commitment = sapling_crypto::primitives::ValueCommitment::<Bls12> {
@@ -1240,8 +1336,9 @@ pub extern "system" fn librustzcash_sapling_output_proof(
         tmp = tmp.negate(); // Outputs subtract from the total.
         tmp = tmp.add(&unsafe { &*ctx }.bvk, &JUBJUB);
 
-        // Update the context
+        // Update the context, including the synthetic blinding factor `bsk`
         unsafe { &mut *ctx }.bvk = tmp;
+        unsafe { &mut *ctx }.use_rcv(rcv, true);
     }
 
     // Write the value commitment to the caller
@@ -1339,21 +1436,60 @@ pub extern "system" fn librustzcash_sapling_binding_sig(
         }
     }
 
+    // Sign
+    let mut rng = OsRng::new().expect("should be able to construct RNG");
+    let sig = create_binding_sig(&bsk, unsafe { &*sighash }, &mut rng);
+
+    // Write out signature
+    sig.write(&mut (unsafe { &mut *result })[..])
+        .expect("result should be 64 bytes");
+
+    true
+}
+
+// Signs `bvk || sighash` with the binding signing key `bsk`, where `bvk` is
+// derived from `bsk`.
+fn create_binding_sig<R: Rng>(
+    bsk: &redjubjub::PrivateKey<Bls12>,
+    sighash: &[u8; 32],
+    rng: &mut R,
+) -> Signature {
+    let bvk = redjubjub::PublicKey::from_private(
+        bsk,
+        FixedGenerators::ValueCommitmentRandomness,
+        &JUBJUB,
+    );
+
     // Construct signature message
     let mut data_to_be_signed = [0u8; 64];
     bvk.0
         .write(&mut data_to_be_signed[0..32])
         .expect("message buffer should be 32 bytes");
-    (&mut data_to_be_signed[32..64]).copy_from_slice(&(unsafe { &*sighash })[..]);
+    (&mut data_to_be_signed[32..64]).copy_from_slice(&sighash[..]);
 
-    // Sign
-    let mut rng = OsRng::new().expect("should be able to construct RNG");
-    let sig = bsk.sign(
+    bsk.sign(
         &data_to_be_signed,
-        &mut rng,
+        rng,
         FixedGenerators::ValueCommitmentRandomness,
         &JUBJUB,
-    );
+    )
+}
+
+#[no_mangle]
+pub extern "system" fn librustzcash_sapling_binding_sig_from_bsk(
+    bsk: *const [c_uchar; 32],
+    sighash: *const [c_uchar; 32],
+    result: *mut [c_uchar; 64],
+) -> bool {
+    // The binding signing key must be a canonical scalar
+    let bsk = match Fs::from_repr(read_fs(&(unsafe { &*bsk })[..])) {
+        Ok(p) => redjubjub::PrivateKey::<Bls12>(p),
+        Err(_) => return false,
+    };
+
+    // Sign
+    let mut rng = OsRng::new().expect("should be able to construct RNG");
+    let sig = create_binding_sig(&bsk, unsafe { &*sighash }, &mut rng);
 
     // Write out signature
     sig.write(&mut (unsafe { &mut *result })[..])
@@ -1377,19 +1513,17 @@ pub extern "system" fn librustzcash_sapling_spend_proof(
     rk_out: *mut [c_uchar; 32],
     zkproof: *mut [c_uchar; GROTH_PROOF_SIZE],
 ) -> bool {
-    let mut rng = OsRng::new().expect("should be able to construct RNG");
-
-    // We create the randomness of the value commitment
-    let rcv = Fs::rand(&mut rng);
+    // The note value cannot exceed the total supply
+    if SaplingNoteValue::from_u64(value).is_none() {
+        return false;
+    }
 
-    // Accumulate the value commitment randomness in the context
-    {
-        let mut tmp = rcv.clone();
-        tmp.add_assign(&unsafe { &*ctx }.bsk);
+    let mut rng = OsRng::new().expect("should be able to construct RNG");
 
-        // Update the context
-        unsafe { &mut *ctx }.bsk = tmp;
-    }
+    // We create the randomness of the value commitment, unless the caller
+    // supplied it. It is only accumulated in the context once the proof is
+    // known to be valid.
+    let rcv = unsafe { &*ctx }.rcv(&mut rng);
 
     // Construct the value commitment
     let value_commitment = ValueCommitment::<Bls12> {
@@ -1464,43 +1598,13 @@ pub extern "system" fn librustzcash_sapling_spend_proof(
 
     // The witness contains the incremental tree witness information, in a
     // weird serialized format.
-    let mut witness = unsafe { &(&*witness)[..] };
-
-    // Skip the first byte, which should be "32" to signify the length of
-    // the following vector of Pedersen hashes.
-    assert_eq!(witness[0], SAPLING_TREE_DEPTH as u8);
-    witness = &witness[1..];
-
-    // Begin to construct the authentication path
-    let mut auth_path = vec![None; SAPLING_TREE_DEPTH];
-
-    // The vector works in reverse
-    for i in (0..SAPLING_TREE_DEPTH).rev() {
-        // skip length of inner vector
-        assert_eq!(witness[0], 32); // the length of a pedersen hash
-        witness = &witness[1..];
-
-        // Grab the sibling node at this depth in the tree
-        let mut sibling = [0u8; 32];
-        sibling.copy_from_slice(&witness[0..32]);
-        witness = &witness[32..];
-
-        // Sibling node should be an element of Fr
-        let sibling = match Fr::from_repr(read_le(&sibling)) {
-            Ok(p) => p,
-            Err(_) => return false,
-        };
-
-        // Set the value in the auth path; we put false here
-        // for now (signifying the position bit) which we'll
-        // fill in later.
-        auth_path[i] = Some((sibling, false));
-    }
-
-    // Read the position from the witness
-    let mut position = witness
-        .read_u64::<LittleEndian>()
-        .expect("should have had index at the end");
+    let witness = match CommitmentTreeWitness::<Bls12>::from_slice(
+        unsafe { &(&*witness)[..] },
+        SAPLING_TREE_DEPTH,
+    ) {
+        Ok(w) => w,
+        Err(_) => return false,
+    };
 
     // Let's compute the nullifier while we have the position
     let note = sapling_crypto::primitives::Note {
@@ -1512,20 +1616,7 @@ pub extern "system" fn librustzcash_sapling_spend_proof(
         r: rcm,
     };
 
-    let nullifier = note.nf(&viewing_key, position, &JUBJUB);
-
-    // Given the position, let's finish constructing the authentication
-    // path
-    for i in 0..SAPLING_TREE_DEPTH {
-        auth_path[i].as_mut().map(|p| p.1 = (position & 1) == 1);
-
-        position >>= 1;
-    }
-
-    // The witness should be empty now; if it wasn't, the caller would
-    // have provided more information than they should have, indicating
-    // a bug downstream
-    assert_eq!(witness.len(), 0);
+    let nullifier = note.nf(&viewing_key, witness.position, &JUBJUB);
 
     // We now have the full witness for our circuit
     let instance = sapling_crypto::circuit::sapling::Spend {
@@ -1535,7 +1626,7 @@ pub extern "system" fn librustzcash_sapling_spend_proof(
         payment_address: Some(payment_address),
         commitment_randomness: Some(rcm),
         ar: Some(ar),
-        auth_path: auth_path,
+        auth_path: witness.circuit_auth_path(),
         anchor: Some(anchor),
     };
 
@@ -1595,8 +1686,9 @@ pub extern "system" fn librustzcash_sapling_spend_proof(
         let mut tmp = value_commitment.clone();
         tmp = tmp.add(&unsafe { &*ctx }.bvk, &JUBJUB);
 
-        // Update the context
+        // Update the context, including the synthetic blinding factor `bsk`
         unsafe { &mut *ctx }.bvk = tmp;
+        unsafe { &mut *ctx }.use_rcv(rcv, false);
     }
 
     // Write value commitment to caller
@@ -1617,11 +1709,56 @@ pub extern "system" fn librustzcash_sapling_proving_ctx_init() -> *mut SaplingPr
     let ctx = Box::new(SaplingProvingContext {
         bsk: Fs::zero(),
         bvk: edwards::Point::zero(),
+        next_rcv: None,
+        last_rcv: None,
     });
 
     Box::into_raw(ctx)
 }
 
+#[no_mangle]
+pub extern "system" fn librustzcash_sapling_proving_ctx_set_rcv(
+    ctx: *mut SaplingProvingContext,
+    rcv: *const [c_uchar; 32],
+) -> bool {
+    let rcv = match Fs::from_repr(read_fs(&(unsafe { &*rcv })[..])) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    unsafe { &mut *ctx }.next_rcv = Some(rcv);
+
+    true
+}
+
+#[no_mangle]
+pub extern "system" fn librustzcash_sapling_proving_ctx_get_rcv(
+    ctx: *const SaplingProvingContext,
+    result: *mut [c_uchar; 32],
+) -> bool {
+    match unsafe { &*ctx }.last_rcv {
+        Some(rcv) => {
+            rcv.into_repr()
+                .write_le(&mut (unsafe { &mut *result })[..])
+                .expect("result should be 32 bytes");
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "system" fn librustzcash_sapling_proving_ctx_get_bsk(
+    ctx: *const SaplingProvingContext,
+    result: *mut [c_uchar; 32],
+) {
+    unsafe { &*ctx }
+        .bsk
+        .into_repr()
+        .write_le(&mut (unsafe { &mut *result })[..])
+        .expect("result should be 32 bytes");
+}
+
 #[no_mangle]
 pub extern "system" fn librustzcash_sapling_proving_ctx_free(ctx: *mut SaplingProvingContext) {
     drop(unsafe { Box::from_raw(ctx) });
@@ -1663,8 +1800,10 @@ pub extern "system" fn librustzcash_zip32_xfvk_derive(
     i: uint32_t,
     xfvk_i: *mut [c_uchar; 169],
 ) -> bool {
-    let xfvk_parent = zip32::ExtendedFullViewingKey::read(&unsafe { *xfvk_parent }[..])
-        .expect("valid ExtendedFullViewingKey");
+    let xfvk_parent = match zip32::ExtendedFullViewingKey::read(&unsafe { *xfvk_parent }[..]) {
+        Ok(xfvk) => xfvk,
+        Err(_) => return false,
+    };
     let i = zip32::ChildIndex::from_index(i);
 
     let xfvk = match xfvk_parent.derive_child(i) {
@@ -1685,8 +1824,10 @@ pub extern "system" fn librustzcash_zip32_xfvk_address(
     j_ret: *mut [c_uchar; 11],
     addr_ret: *mut [c_uchar; 43],
 ) -> bool {
-    let xfvk = zip32::ExtendedFullViewingKey::read(&unsafe { *xfvk }[..])
-        .expect("valid ExtendedFullViewingKey");
+    let xfvk = match zip32::ExtendedFullViewingKey::read(&unsafe { *xfvk }[..]) {
+        Ok(xfvk) => xfvk,
+        Err(_) => return false,
+    };
     let j = zip32::DiversifierIndex(unsafe { *j });
 
     let addr = match xfvk.address(j) {
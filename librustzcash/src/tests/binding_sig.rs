@@ -0,0 +1,173 @@
+use pairing::{bls12_381::Bls12, Field, PrimeField, PrimeFieldRepr};
+use rand::{Rand, SeedableRng, XorShiftRng};
+use sapling_crypto::{
+    jubjub::{edwards, fs::Fs},
+    primitives::ValueCommitment,
+    test_vectors::KEY_COMPONENTS,
+};
+
+use super::JUBJUB;
+
+use {
+    librustzcash_sapling_binding_sig_from_bsk, librustzcash_sapling_final_check,
+    librustzcash_sapling_proving_ctx_free, librustzcash_sapling_proving_ctx_get_bsk,
+    librustzcash_sapling_proving_ctx_get_rcv, librustzcash_sapling_proving_ctx_init,
+    librustzcash_sapling_proving_ctx_set_rcv, librustzcash_sapling_spend_proof,
+    SaplingVerificationContext, GROTH_PROOF_SIZE, SAPLING_TREE_DEPTH,
+};
+
+#[test]
+fn binding_sig_from_explicit_bsk() {
+    let params = &*JUBJUB;
+    let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let spends = [
+        ValueCommitment::<Bls12> {
+            value: 10,
+            randomness: Fs::rand(&mut rng),
+        },
+        ValueCommitment {
+            value: 5,
+            randomness: Fs::rand(&mut rng),
+        },
+    ];
+    let output = ValueCommitment {
+        value: 12,
+        randomness: Fs::rand(&mut rng),
+    };
+
+    // bsk is the sum of the Spend rcv values minus the Output rcv values,
+    // and the verifier accumulates bvk from the value commitments.
+    let mut bsk = Fs::zero();
+    let mut bvk = edwards::Point::zero();
+    for spend in spends.iter() {
+        bsk.add_assign(&spend.randomness);
+        bvk = bvk.add(&spend.cm(params).into(), params);
+    }
+    bsk.sub_assign(&output.randomness);
+    bvk = bvk.add(&output.cm(params).negate().into(), params);
+
+    let mut bsk_bytes = [0u8; 32];
+    bsk.into_repr().write_le(&mut bsk_bytes[..]).unwrap();
+
+    let sighash = [0xab; 32];
+    let mut sig = [0u8; 64];
+    assert!(librustzcash_sapling_binding_sig_from_bsk(
+        &bsk_bytes, &sighash, &mut sig
+    ));
+
+    let mut ctx = SaplingVerificationContext { bvk };
+    assert!(librustzcash_sapling_final_check(
+        &mut ctx, 3, &sig, &sighash
+    ));
+    assert!(!librustzcash_sapling_final_check(
+        &mut ctx, 4, &sig, &sighash
+    ));
+    assert!(!librustzcash_sapling_final_check(
+        &mut ctx,
+        3,
+        &sig,
+        &[0xcd; 32]
+    ));
+
+    // A non-canonical bsk is rejected
+    assert!(!librustzcash_sapling_binding_sig_from_bsk(
+        &[0xff; 32],
+        &sighash,
+        &mut sig
+    ));
+}
+
+#[test]
+fn caller_supplied_rcv() {
+    let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let spend_rcv = Fs::rand(&mut rng);
+    let output_rcv = Fs::rand(&mut rng);
+
+    let ctx = librustzcash_sapling_proving_ctx_init();
+    let mut rcv = [0u8; 32];
+    assert!(!librustzcash_sapling_proving_ctx_get_rcv(ctx, &mut rcv));
+
+    // Supplied values are used once, by the next proof
+    for &(expected, output) in &[(spend_rcv, false), (output_rcv, true)] {
+        let mut supplied = [0u8; 32];
+        expected.into_repr().write_le(&mut supplied[..]).unwrap();
+        assert!(librustzcash_sapling_proving_ctx_set_rcv(ctx, &supplied));
+
+        let next = unsafe { &*ctx }.rcv(&mut rng);
+        assert_eq!(next, expected);
+        unsafe { &mut *ctx }.use_rcv(next, output);
+        assert!(librustzcash_sapling_proving_ctx_get_rcv(ctx, &mut rcv));
+        assert_eq!(rcv, supplied);
+    }
+    // Otherwise a fresh value is sampled
+    let sampled_rcv = unsafe { &*ctx }.rcv(&mut rng);
+    unsafe { &mut *ctx }.use_rcv(sampled_rcv, false);
+    assert!(sampled_rcv != output_rcv);
+
+    // Non-canonical values are rejected
+    assert!(!librustzcash_sapling_proving_ctx_set_rcv(ctx, &[0xff; 32]));
+
+    // bsk is the sum of the Spend rcv values minus the Output rcv values
+    let mut expected_bsk = spend_rcv;
+    expected_bsk.sub_assign(&output_rcv);
+    expected_bsk.add_assign(&sampled_rcv);
+    let mut expected = [0u8; 32];
+    expected_bsk
+        .into_repr()
+        .write_le(&mut expected[..])
+        .unwrap();
+
+    let mut bsk = [0u8; 32];
+    librustzcash_sapling_proving_ctx_get_bsk(ctx, &mut bsk);
+    assert_eq!(bsk, expected);
+
+    librustzcash_sapling_proving_ctx_free(ctx);
+}
+
+#[test]
+fn failed_spend_leaves_context_unchanged() {
+    let tv = &KEY_COMPONENTS[0];
+    let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+    let supplied_rcv = Fs::rand(&mut rng);
+    let mut supplied = [0u8; 32];
+    supplied_rcv
+        .into_repr()
+        .write_le(&mut supplied[..])
+        .unwrap();
+
+    let ctx = librustzcash_sapling_proving_ctx_init();
+    assert!(librustzcash_sapling_proving_ctx_set_rcv(ctx, &supplied));
+
+    // An invalid ak, and then an invalid witness, both fail before proving
+    let witness = [0u8; 1 + 33 * SAPLING_TREE_DEPTH + 8];
+    for ak in &[[0xff; 32], tv.ak] {
+        let mut cv = [0u8; 32];
+        let mut rk = [0u8; 32];
+        let mut zkproof = [0u8; GROTH_PROOF_SIZE];
+        assert!(!librustzcash_sapling_spend_proof(
+            ctx,
+            ak,
+            &tv.nsk,
+            &tv.default_d,
+            &tv.note_r,
+            &[0; 32],
+            tv.note_v,
+            &[0; 32],
+            &witness,
+            &mut cv,
+            &mut rk,
+            &mut zkproof,
+        ));
+    }
+
+    // The supplied rcv is still pending, and nothing was accumulated
+    assert_eq!(unsafe { &*ctx }.next_rcv, Some(supplied_rcv));
+    let mut rcv = [0u8; 32];
+    assert!(!librustzcash_sapling_proving_ctx_get_rcv(ctx, &mut rcv));
+    let mut bsk = [0xff; 32];
+    librustzcash_sapling_proving_ctx_get_bsk(ctx, &mut bsk);
+    assert_eq!(bsk, [0; 32]);
+
+    librustzcash_sapling_proving_ctx_free(ctx);
+}
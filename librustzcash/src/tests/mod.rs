@@ -2,10 +2,14 @@ use sapling_crypto::jubjub::{FixedGenerators, JubjubParams};
 
 use super::JUBJUB;
 
+mod binding_sig;
 mod key_agreement;
 mod key_components;
 mod notes;
 mod signatures;
+mod spend_proof;
+mod sprout;
+mod zip32;
 
 #[test]
 fn sapling_generators() {
@@ -1,3 +1,5 @@
+use zcash_primitives::amount::MAX_MONEY;
+
 use librustzcash_sapling_compute_cm;
 use librustzcash_sapling_compute_nf;
 
@@ -646,8 +648,31 @@ fn notes() {
     ];
 
     for tv in test_vectors {
-        // Compute commitment and compare with test vector
         let mut result = [0u8; 32];
+
+        // Notes above the total supply are rejected
+        if tv.note_v > MAX_MONEY as u64 {
+            assert!(!librustzcash_sapling_compute_cm(
+                &tv.default_d,
+                &tv.default_pk_d,
+                tv.note_v,
+                &tv.note_r,
+                &mut result
+            ));
+            assert!(!librustzcash_sapling_compute_nf(
+                &tv.default_d,
+                &tv.default_pk_d,
+                tv.note_v,
+                &tv.note_r,
+                &tv.ak,
+                &tv.nk,
+                tv.note_pos,
+                &mut result
+            ));
+            continue;
+        }
+
+        // Compute commitment and compare with test vector
         assert!(librustzcash_sapling_compute_cm(
             &tv.default_d,
             &tv.default_pk_d,
@@ -0,0 +1,53 @@
+use bellman::groth16::Proof;
+use pairing::{
+    bls12_381::{Bls12, G1Affine, G2Affine},
+    CurveAffine,
+};
+use sapling_crypto::test_vectors::KEY_COMPONENTS;
+
+use {librustzcash_sapling_check_spend_proof, GROTH_PROOF_SIZE};
+
+/// Returns a proof that is well-formed, but not valid for any statement.
+fn well_formed_proof() -> [u8; GROTH_PROOF_SIZE] {
+    let proof = Proof::<Bls12> {
+        a: G1Affine::one(),
+        b: G2Affine::one(),
+        c: G1Affine::one(),
+    };
+    let mut zkproof = [0u8; GROTH_PROOF_SIZE];
+    proof.write(&mut zkproof[..]).unwrap();
+    zkproof
+}
+
+#[test]
+fn malformed_spend_is_rejected() {
+    // Any prime-order point is a well-formed cv or rk
+    let point = KEY_COMPONENTS[0].ak;
+    let anchor = [0u8; 32];
+    let nullifier = [0u8; 32];
+    let zkproof = well_formed_proof();
+
+    let check =
+        |cv: &[u8; 32], anchor: &[u8; 32], rk: &[u8; 32], zkproof: &[u8; GROTH_PROOF_SIZE]| {
+            librustzcash_sapling_check_spend_proof(cv, anchor, &nullifier, rk, zkproof)
+        };
+
+    // Without the parameters loaded, even a well-formed spend fails
+    assert!(!check(&point, &anchor, &point, &zkproof));
+
+    // A non-canonical or small-order cv
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    assert!(!check(&[0xff; 32], &anchor, &point, &zkproof));
+    assert!(!check(&identity, &anchor, &point, &zkproof));
+
+    // A non-canonical or small-order rk
+    assert!(!check(&point, &anchor, &[0xff; 32], &zkproof));
+    assert!(!check(&point, &anchor, &identity, &zkproof));
+
+    // A non-canonical anchor
+    assert!(!check(&point, &[0xff; 32], &point, &zkproof));
+
+    // A proof that does not encode curve points
+    assert!(!check(&point, &anchor, &point, &[0xff; GROTH_PROOF_SIZE]));
+}
@@ -0,0 +1,59 @@
+use sapling_crypto::circuit::sprout::TREE_DEPTH;
+
+use librustzcash_sprout_prove;
+
+const AUTH_LEN: usize = 1 + 33 * TREE_DEPTH + 8;
+
+/// Returns a well-formed authentication path, with zero siblings, at
+/// position zero.
+fn auth_path() -> [u8; AUTH_LEN] {
+    let mut auth = [0u8; AUTH_LEN];
+    auth[0] = TREE_DEPTH as u8;
+    for i in 0..TREE_DEPTH {
+        auth[1 + 33 * i] = 32;
+    }
+    auth
+}
+
+fn prove(in_auth1: &[u8; AUTH_LEN], in_auth2: &[u8; AUTH_LEN]) -> bool {
+    let mut proof_out = [0u8; 192];
+    librustzcash_sprout_prove(
+        &mut proof_out,
+        &[0; 32],
+        &[0; 32],
+        &[0; 32],
+        &[0; 32],
+        0,
+        &[0; 32],
+        &[0; 32],
+        in_auth1,
+        &[0; 32],
+        0,
+        &[0; 32],
+        &[0; 32],
+        in_auth2,
+        &[0; 32],
+        0,
+        &[0; 32],
+        &[0; 32],
+        0,
+        &[0; 32],
+        0,
+        0,
+    )
+}
+
+#[test]
+fn malformed_auth_path_is_rejected() {
+    // The wrong depth in either input
+    let mut bad_depth = auth_path();
+    bad_depth[0] = TREE_DEPTH as u8 + 1;
+    assert!(!prove(&bad_depth, &auth_path()));
+    assert!(!prove(&auth_path(), &bad_depth));
+
+    // The wrong length for a sibling
+    let mut bad_sibling = auth_path();
+    bad_sibling[1 + 33 * 5] = 31;
+    assert!(!prove(&bad_sibling, &auth_path()));
+    assert!(!prove(&auth_path(), &bad_sibling));
+}
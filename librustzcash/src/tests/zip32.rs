@@ -0,0 +1,37 @@
+use zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
+
+use {librustzcash_zip32_xfvk_address, librustzcash_zip32_xfvk_derive};
+
+#[test]
+fn invalid_xfvk_is_rejected() {
+    let xsk_m = ExtendedSpendingKey::master(&[0; 32]);
+    let xfvk_m = ExtendedFullViewingKey::from(&xsk_m);
+
+    let mut valid = [0u8; 169];
+    xfvk_m.write(&mut valid[..]).unwrap();
+
+    let mut j_ret = [0u8; 11];
+    let mut addr_ret = [0u8; 43];
+    let mut xfvk_i = [0u8; 169];
+    assert!(librustzcash_zip32_xfvk_address(
+        &valid,
+        &[0; 11],
+        &mut j_ret,
+        &mut addr_ret
+    ));
+    assert!(librustzcash_zip32_xfvk_derive(&valid, 0, &mut xfvk_i));
+
+    // Replace ak with an encoding that is not a valid point
+    let mut invalid = valid;
+    for b in invalid[41..73].iter_mut() {
+        *b = 0xff;
+    }
+
+    assert!(!librustzcash_zip32_xfvk_address(
+        &invalid,
+        &[0; 11],
+        &mut j_ret,
+        &mut addr_ret
+    ));
+    assert!(!librustzcash_zip32_xfvk_derive(&invalid, 0, &mut xfvk_i));
+}
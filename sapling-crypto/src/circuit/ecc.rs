@@ -39,19 +39,38 @@ pub struct EdwardsPoint<E: Engine> {
 /// Perform a fixed-base scalar multiplication with
 /// `by` being in little-endian bit order.
 pub fn fixed_base_multiplication<E, CS>(
-    mut cs: CS,
+    cs: CS,
     base: FixedGenerators,
     by: &[Boolean],
     params: &E::Params
 ) -> Result<EdwardsPoint<E>, SynthesisError>
     where CS: ConstraintSystem<E>,
           E: JubjubEngine
+{
+    fixed_base_multiplication_with_table(
+        cs,
+        params.circuit_generators(base),
+        by,
+        params
+    )
+}
+
+/// Perform a fixed-base scalar multiplication with
+/// the given window tables for the base.
+pub(crate) fn fixed_base_multiplication_with_table<E, CS>(
+    mut cs: CS,
+    windows: &[Vec<(E::Fr, E::Fr)>],
+    by: &[Boolean],
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where CS: ConstraintSystem<E>,
+          E: JubjubEngine
 {
     // Represents the result of the multiplication
     let mut result = None;
 
     for (i, (chunk, window)) in by.chunks(3)
-                                  .zip(params.circuit_generators(base).iter())
+                                  .zip(windows.iter())
                                   .enumerate()
     {
         let chunk_a = chunk.get(0).map(|e| e.clone()).unwrap_or(Boolean::constant(false));
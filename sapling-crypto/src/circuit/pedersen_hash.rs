@@ -1,5 +1,6 @@
 use super::*;
 use super::ecc::{
+    fixed_base_multiplication_with_table,
     MontgomeryPoint,
     EdwardsPoint
 };
@@ -9,7 +10,7 @@ use bellman::{
     ConstraintSystem
 };
 use super::lookup::*;
-pub use pedersen_hash::Personalization;
+pub use pedersen_hash::{PedersenGenerators, Personalization};
 
 impl Personalization {
     fn get_constant_bools(&self) -> Vec<Boolean> {
@@ -21,7 +22,7 @@ impl Personalization {
 }
 
 pub fn pedersen_hash<E: JubjubEngine, CS>(
-    mut cs: CS,
+    cs: CS,
     personalization: Personalization,
     bits: &[Boolean],
     params: &E::Params
@@ -31,13 +32,83 @@ pub fn pedersen_hash<E: JubjubEngine, CS>(
     let personalization = personalization.get_constant_bools();
     assert_eq!(personalization.len(), 6);
 
+    hash_segments(
+        cs,
+        personalization.iter().chain(bits.iter()),
+        params.pedersen_circuit_generators(),
+        params
+    )
+}
+
+/// Computes the Pedersen hash of `bits` with application generators, as
+/// `PedersenGenerators::hash` does outside of the circuit.
+///
+/// Panics if `bits` is empty, longer than `generators.max_bits()`, or not a
+/// multiple of three bits long.
+pub fn pedersen_hash_with_generators<E: JubjubEngine, CS>(
+    cs: CS,
+    generators: &PedersenGenerators<E>,
+    bits: &[Boolean],
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    assert!(!bits.is_empty() && bits.len() <= generators.max_bits());
+    assert!(bits.len() % 3 == 0, "number of bits is not a multiple of three");
+
+    hash_segments(cs, bits.iter(), generators.circuit_generators(), params)
+}
+
+/// Computes the Pedersen commitment to `bits` with the given little-endian
+/// randomness bits, as `PedersenGenerators::commit` does outside of the
+/// circuit.
+///
+/// Panics if `bits` is empty, longer than `generators.max_bits()`, or not a
+/// multiple of three bits long.
+pub fn pedersen_commitment<E: JubjubEngine, CS>(
+    mut cs: CS,
+    generators: &PedersenGenerators<E>,
+    bits: &[Boolean],
+    randomness: &[Boolean],
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where CS: ConstraintSystem<E>
+{
+    let hash = pedersen_hash_with_generators(
+        cs.namespace(|| "hash"),
+        generators,
+        bits,
+        params
+    )?;
+
+    let randomness = fixed_base_multiplication_with_table(
+        cs.namespace(|| "randomness"),
+        generators.randomness_circuit_generators(),
+        randomness,
+        params
+    )?;
+
+    hash.add(cs.namespace(|| "randomization"), &randomness, params)
+}
+
+fn hash_segments<'a, E: JubjubEngine, CS, I>(
+    mut cs: CS,
+    bits: I,
+    segment_generators: &[Vec<Vec<(E::Fr, E::Fr)>>],
+    params: &E::Params
+) -> Result<EdwardsPoint<E>, SynthesisError>
+    where CS: ConstraintSystem<E>,
+          I: Iterator<Item=&'a Boolean>
+{
     let mut edwards_result = None;
-    let mut bits = personalization.iter().chain(bits.iter());
-    let mut segment_generators = params.pedersen_circuit_generators().iter();
+    let mut bits = bits.peekable();
+    let mut segment_generators = segment_generators.iter();
     let boolean_false = Boolean::constant(false);
 
     let mut segment_i = 0;
-    loop {
+    // Stop before taking the generators for a segment with no bits, so that
+    // the input can fill every segment
+    while bits.peek().is_some() {
         let mut segment_result = None;
         let mut segment_windows = &segment_generators.next()
                                                      .expect("enough segments")[..];
@@ -191,4 +262,121 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_pedersen_commitment_with_generators() {
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let generators = PedersenGenerators::<Bls12>::new(b"Test_PH_", 400, params);
+
+        // Rounded up to three segments of 189 bits
+        assert_eq!(generators.max_bits(), 567);
+
+        for &length in &[3, 186, 189, 192, 567] {
+            let input: Vec<bool> = (0..length).map(|_| rng.gen()).collect();
+            let r: <Bls12 as JubjubEngine>::Fs = rng.gen();
+
+            let mut cs = TestConstraintSystem::<Bls12>::new();
+
+            let input_bools: Vec<Boolean> = input.iter().enumerate().map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.namespace(|| format!("input {}", i)), Some(*b)).unwrap()
+                )
+            }).collect();
+
+            let r_bools = ::circuit::boolean::field_into_boolean_vec_le(
+                cs.namespace(|| "r"),
+                Some(r)
+            ).unwrap();
+
+            let res = pedersen_commitment(
+                cs.namespace(|| "pedersen commitment"),
+                &generators,
+                &input_bools,
+                &r_bools,
+                params
+            ).unwrap();
+
+            assert!(cs.is_satisfied());
+
+            let expected = generators.commit(input.clone(), r, params).into_xy();
+
+            assert_eq!(res.get_x().get_value().unwrap(), expected.0);
+            assert_eq!(res.get_y().get_value().unwrap(), expected.1);
+
+            // The commitment is the hash, randomized
+            let hash = generators.hash(input.clone(), params);
+            assert_eq!(
+                hash.add(&generators.randomness_generator().mul(r, params), params).into_xy(),
+                expected
+            );
+
+            // Another personalization gives an unrelated hash
+            let other = PedersenGenerators::<Bls12>::new(b"Test_PH2", 400, params);
+            assert!(other.hash(input, params) != hash);
+        }
+    }
+
+    #[test]
+    fn test_pedersen_generators_bind_length() {
+        let params = &JubjubBls12::new();
+        let generators = PedersenGenerators::<Bls12>::new(b"Test_PH_", 400, params);
+
+        // Appending a zero chunk changes the hash, within a segment and
+        // across a segment boundary
+        let hash = |bits: &[bool]| generators.hash(bits.iter().cloned(), params);
+        assert!(hash(&[true, false, false]) != hash(&[true, false, false, false, false, false]));
+        let full: Vec<bool> = (0..189).map(|i| i % 5 == 0).collect();
+        let mut extended = full.clone();
+        extended.extend_from_slice(&[false, false, false]);
+        assert!(hash(&full) != hash(&extended));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a multiple of three")]
+    fn test_pedersen_generators_reject_partial_chunk() {
+        let params = &JubjubBls12::new();
+        let generators = PedersenGenerators::<Bls12>::new(b"Test_PH_", 400, params);
+
+        // Without the check, this would hash the same as [true, false, false]
+        generators.hash(vec![true], params);
+    }
+
+    #[test]
+    #[should_panic(expected = "no bits to hash")]
+    fn test_pedersen_generators_reject_empty_input() {
+        let params = &JubjubBls12::new();
+        let generators = PedersenGenerators::<Bls12>::new(b"Test_PH_", 400, params);
+        let mut rng = XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        // The hash of no bits would be the identity, leaving a commitment
+        // to nothing but its randomness
+        generators.commit(vec![], rng.gen(), params);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a multiple of three")]
+    fn test_pedersen_commitment_rejects_partial_chunk() {
+        let params = &JubjubBls12::new();
+        let generators = PedersenGenerators::<Bls12>::new(b"Test_PH_", 400, params);
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+
+        let input_bools = vec![Boolean::constant(true), Boolean::constant(false)];
+        let r_bools = vec![Boolean::constant(true)];
+
+        pedersen_commitment(
+            cs.namespace(|| "pedersen commitment"),
+            &generators,
+            &input_bools,
+            &r_bools,
+            params
+        ).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "reserved for Sapling")]
+    fn test_pedersen_generators_reject_sapling_personalization() {
+        let params = &JubjubBls12::new();
+        PedersenGenerators::<Bls12>::new(::constants::PEDERSEN_HASH_GENERATORS_PERSONALIZATION, 100, params);
+    }
 }
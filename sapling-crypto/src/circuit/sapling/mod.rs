@@ -792,11 +792,12 @@ fn test_output_circuit_with_bls12_381() {
             assert_eq!(cs.num_constraints(), 7827);
             assert_eq!(cs.hash(), "c26d5cdfe6ccd65c03390902c02e11393ea6bb96aae32a7f2ecb12eb9103faee");
 
-            let expected_cm = payment_address.create_note(
-                value_commitment.value,
-                commitment_randomness,
-                params
-            ).expect("should be valid").cm(params);
+            let expected_cm = ::primitives::Note {
+                value: value_commitment.value,
+                g_d: payment_address.g_d(params).expect("should be valid"),
+                pk_d: payment_address.pk_d.clone(),
+                r: commitment_randomness
+            }.cm(params);
 
             let expected_value_cm = value_commitment.cm(params).into_xy();
 
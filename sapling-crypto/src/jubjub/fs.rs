@@ -3,7 +3,7 @@ use pairing::{BitIterator, Field, PrimeField, SqrtField, PrimeFieldRepr, PrimeFi
 use pairing::LegendreSymbol::*;
 use pairing::{adc, sbb, mac_with_carry};
 
-use super::ToUniform;
+use super::{ToUniform, Zeroize};
 
 // s = 6554484396890773809930967563523245729705921265872317281365359162392183254199
 const MODULUS: FsRepr = FsRepr([0xd0970e5ed6f72cb7, 0xa6682093ccc81082, 0x6673b0101343b00, 0xe7db4ea6533afa9]);
@@ -578,6 +578,21 @@ impl ToUniform for Fs {
     }
 }
 
+impl Zeroize for FsRepr {
+    fn zeroize(&mut self) {
+        // The write is volatile so that it is not optimized away as a dead
+        // store when the value is about to be dropped.
+        unsafe { ::std::ptr::write_volatile(self, FsRepr::default()) };
+        ::std::sync::atomic::compiler_fence(::std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+impl Zeroize for Fs {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl SqrtField for Fs {
 
     fn legendre(&self) -> LegendreSymbol {
@@ -622,6 +637,24 @@ fn test_neg_one() {
 #[cfg(test)]
 use rand::{SeedableRng, XorShiftRng, Rand};
 
+#[test]
+fn test_fs_zeroize() {
+    let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let mut a = Fs::rand(&mut rng);
+    assert!(!a.is_zero());
+    a.zeroize();
+    assert!(a.is_zero());
+
+    let mut a = FsRepr::rand(&mut rng);
+    a.zeroize();
+    assert!(a.is_zero());
+
+    let mut bytes = [0xffu8; 112];
+    bytes[..].zeroize();
+    assert!(bytes.iter().all(|b| *b == 0));
+}
+
 #[test]
 fn test_fs_repr_ordering() {
     fn assert_equality(a: FsRepr, b: FsRepr) {
@@ -89,12 +89,29 @@ pub trait ToUniform {
     fn to_uniform(digest: &[u8]) -> Self;
 }
 
+/// A value that can be overwritten with zero, so that a secret does not
+/// remain in memory after it is no longer needed.
+pub trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+impl Zeroize for [u8] {
+    fn zeroize(&mut self) {
+        // The writes are volatile so that they are not optimized away as
+        // dead stores when the buffer is about to go out of scope.
+        for b in self.iter_mut() {
+            unsafe { ::std::ptr::write_volatile(b, 0) };
+        }
+        ::std::sync::atomic::compiler_fence(::std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
 /// This is an extension to the pairing Engine trait which
 /// offers a scalar field for the embedded curve (Jubjub)
 /// and some pre-computed parameters.
 pub trait JubjubEngine: Engine {
     /// The scalar field of the Jubjub curve
-    type Fs: PrimeField + SqrtField + ToUniform;
+    type Fs: PrimeField + SqrtField + ToUniform + Zeroize;
     /// The parameters of Jubjub and the Sapling protocol
     type Params: JubjubParams<Self>;
 }
@@ -185,6 +202,136 @@ impl JubjubParams<Bls12> for JubjubBls12 {
     }
 }
 
+/// Returns the first valid group hash of `m` followed by a counter byte,
+/// trying counters from zero upwards.
+pub(crate) fn find_group_hash<E: JubjubEngine>(
+    m: &[u8],
+    personalization: &[u8; 8],
+    params: &E::Params
+) -> edwards::Point<E, PrimeOrder>
+{
+    let mut tag = m.to_vec();
+    let i = tag.len();
+    tag.push(0u8);
+
+    loop {
+        let gh = group_hash(
+            &tag,
+            personalization,
+            params
+        );
+
+        // We don't want to overflow and start reusing generators
+        assert!(tag[i] != u8::max_value());
+        tag[i] += 1;
+
+        if let Some(gh) = gh {
+            break gh;
+        }
+    }
+}
+
+/// Creates the exp table used to compute Pedersen hashes outside of the
+/// circuit with the given segment generators.
+pub(crate) fn pedersen_hash_exp_table<E: JubjubEngine>(
+    generators: &[edwards::Point<E, PrimeOrder>],
+    params: &E::Params
+) -> Vec<Vec<Vec<edwards::Point<E, PrimeOrder>>>>
+{
+    let mut pedersen_hash_exp = vec![];
+
+    for g in generators {
+        let mut g = g.clone();
+
+        let window = E::Params::pedersen_hash_exp_window_size();
+
+        let mut tables = vec![];
+
+        let mut num_bits = 0;
+        while num_bits <= E::Fs::NUM_BITS {
+            let mut table = Vec::with_capacity(1 << window);
+
+            let mut base = edwards::Point::zero();
+
+            for _ in 0..(1 << window) {
+                table.push(base.clone());
+                base = base.add(&g, params);
+            }
+
+            tables.push(table);
+            num_bits += window;
+
+            for _ in 0..window {
+                g = g.double(params);
+            }
+        }
+
+        pedersen_hash_exp.push(tables);
+    }
+
+    pedersen_hash_exp
+}
+
+/// Creates the 2-bit window table lookups for each 4-bit "chunk" in each
+/// segment of a Pedersen hash with the given segment generators.
+pub(crate) fn pedersen_circuit_generators<E: JubjubEngine>(
+    generators: &[edwards::Point<E, PrimeOrder>],
+    params: &E::Params
+) -> Vec<Vec<Vec<(E::Fr, E::Fr)>>>
+{
+    let mut pedersen_circuit_generators = vec![];
+
+    // Process each segment
+    for gen in generators {
+        let mut gen = montgomery::Point::from_edwards(gen, params);
+        let mut windows = vec![];
+        for _ in 0..params.pedersen_hash_chunks_per_generator() {
+            // Create (x, y) coeffs for this chunk
+            let mut coeffs = vec![];
+            let mut g = gen.clone();
+
+            // coeffs = g, g*2, g*3, g*4
+            for _ in 0..4 {
+                coeffs.push(g.into_xy().expect("cannot produce O"));
+                g = g.add(&gen, params);
+            }
+            windows.push(coeffs);
+
+            // Our chunks are separated by 2 bits to prevent overlap.
+            for _ in 0..4 {
+                gen = gen.double(params);
+            }
+        }
+        pedersen_circuit_generators.push(windows);
+    }
+
+    pedersen_circuit_generators
+}
+
+/// Creates the 3-bit window table lookups for fixed-base exp of `gen`.
+pub(crate) fn fixed_base_circuit_generators<E: JubjubEngine>(
+    gen: &edwards::Point<E, PrimeOrder>,
+    params: &E::Params
+) -> Vec<Vec<(E::Fr, E::Fr)>>
+{
+    let mut gen = gen.clone();
+    let mut windows = vec![];
+    for _ in 0..params.fixed_base_chunks_per_generator() {
+        let mut coeffs = vec![(E::Fr::zero(), E::Fr::one())];
+        let mut g = gen.clone();
+        for _ in 0..7 {
+            coeffs.push(g.into_xy());
+            g = g.add(&gen, params);
+        }
+        windows.push(coeffs);
+
+        // gen = gen * 8
+        gen = g;
+    }
+
+    windows
+}
+
 impl JubjubBls12 {
     pub fn new() -> Self {
         let montgomery_a = Fr::from_str("40962").unwrap();
@@ -209,33 +356,6 @@ impl JubjubBls12 {
             fixed_base_circuit_generators: vec![],
         };
 
-        fn find_group_hash<E: JubjubEngine>(
-            m: &[u8],
-            personalization: &[u8; 8],
-            params: &E::Params
-        ) -> edwards::Point<E, PrimeOrder>
-        {
-            let mut tag = m.to_vec();
-            let i = tag.len();
-            tag.push(0u8);
-
-            loop {
-                let gh = group_hash(
-                    &tag,
-                    personalization,
-                    params
-                );
-
-                // We don't want to overflow and start reusing generators
-                assert!(tag[i] != u8::max_value());
-                tag[i] += 1;
-
-                if let Some(gh) = gh {
-                    break gh;
-                }
-            }
-        }
-
         // Create the bases for the Pedersen hashes
         {
             let mut pedersen_hash_generators = vec![];
@@ -272,40 +392,8 @@ impl JubjubBls12 {
         }
 
         // Create the exp table for the Pedersen hash generators
-        {
-            let mut pedersen_hash_exp = vec![];
-
-            for g in &tmp_params.pedersen_hash_generators {
-                let mut g = g.clone();
-
-                let window = JubjubBls12::pedersen_hash_exp_window_size();
-
-                let mut tables = vec![];
-
-                let mut num_bits = 0;
-                while num_bits <= fs::Fs::NUM_BITS {
-                    let mut table = Vec::with_capacity(1 << window);
-
-                    let mut base = edwards::Point::zero();
-
-                    for _ in 0..(1 << window) {
-                        table.push(base.clone());
-                        base = base.add(&g, &tmp_params);
-                    }
-
-                    tables.push(table);
-                    num_bits += window;
-
-                    for _ in 0..window {
-                        g = g.double(&tmp_params);
-                    }
-                }
-
-                pedersen_hash_exp.push(tables);
-            }
-
-            tmp_params.pedersen_hash_exp = pedersen_hash_exp;
-        }
+        tmp_params.pedersen_hash_exp =
+            pedersen_hash_exp_table(&tmp_params.pedersen_hash_generators, &tmp_params);
 
         // Create the bases for other parts of the protocol
         {
@@ -347,60 +435,15 @@ impl JubjubBls12 {
 
         // Create the 2-bit window table lookups for each 4-bit
         // "chunk" in each segment of the Pedersen hash
-        {
-            let mut pedersen_circuit_generators = vec![];
-
-            // Process each segment
-            for mut gen in tmp_params.pedersen_hash_generators.iter().cloned() {
-                let mut gen = montgomery::Point::from_edwards(&gen, &tmp_params);
-                let mut windows = vec![];
-                for _ in 0..tmp_params.pedersen_hash_chunks_per_generator() {
-                    // Create (x, y) coeffs for this chunk
-                    let mut coeffs = vec![];
-                    let mut g = gen.clone();
-
-                    // coeffs = g, g*2, g*3, g*4
-                    for _ in 0..4 {
-                        coeffs.push(g.into_xy().expect("cannot produce O"));
-                        g = g.add(&gen, &tmp_params);
-                    }
-                    windows.push(coeffs);
-
-                    // Our chunks are separated by 2 bits to prevent overlap.
-                    for _ in 0..4 {
-                        gen = gen.double(&tmp_params);
-                    }
-                }
-                pedersen_circuit_generators.push(windows);
-            }
-
-            tmp_params.pedersen_circuit_generators = pedersen_circuit_generators;
-        }
+        tmp_params.pedersen_circuit_generators =
+            pedersen_circuit_generators(&tmp_params.pedersen_hash_generators, &tmp_params);
 
         // Create the 3-bit window table lookups for fixed-base
         // exp of each base in the protocol.
-        {
-            let mut fixed_base_circuit_generators = vec![];
-
-            for mut gen in tmp_params.fixed_base_generators.iter().cloned() {
-                let mut windows = vec![];
-                for _ in 0..tmp_params.fixed_base_chunks_per_generator() {
-                    let mut coeffs = vec![(Fr::zero(), Fr::one())];
-                    let mut g = gen.clone();
-                    for _ in 0..7 {
-                        coeffs.push(g.into_xy());
-                        g = g.add(&gen, &tmp_params);
-                    }
-                    windows.push(coeffs);
-
-                    // gen = gen * 8
-                    gen = g;
-                }
-                fixed_base_circuit_generators.push(windows);
-            }
-
-            tmp_params.fixed_base_circuit_generators = fixed_base_circuit_generators;
-        }
+        tmp_params.fixed_base_circuit_generators =
+            tmp_params.fixed_base_generators.iter()
+                      .map(|gen| fixed_base_circuit_generators(gen, &tmp_params))
+                      .collect();
 
         tmp_params
     }
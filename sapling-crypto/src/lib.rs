@@ -16,7 +16,15 @@ pub mod jubjub;
 pub mod group_hash;
 pub mod circuit;
 pub mod pedersen_hash;
+pub mod merkle_tree;
 pub mod primitives;
 pub mod constants;
 pub mod redjubjub;
 pub mod util;
+pub mod test_vectors;
+pub mod testing;
+pub mod musig;
+pub mod threshold;
+
+#[cfg(test)]
+mod multisig_tests;
@@ -0,0 +1,231 @@
+//! Authentication paths in the Sapling note commitment tree.
+
+use pairing::{BitIterator, PrimeField, PrimeFieldRepr};
+
+use jubjub::JubjubEngine;
+use pedersen_hash::{pedersen_hash, Personalization};
+
+/// The depth of the Sapling note commitment tree.
+pub const SAPLING_COMMITMENT_TREE_DEPTH: usize = 32;
+
+/// Computes the Merkle tree node above `lhs` and `rhs` at the given depth,
+/// where depth 0 is just above the leaves.
+pub fn merkle_hash<E: JubjubEngine>(
+    depth: usize,
+    lhs: &E::Fr,
+    rhs: &E::Fr,
+    params: &E::Params
+) -> E::Fr
+{
+    let mut lhs: Vec<bool> = BitIterator::new(lhs.into_repr()).collect();
+    let mut rhs: Vec<bool> = BitIterator::new(rhs.into_repr()).collect();
+
+    lhs.reverse();
+    rhs.reverse();
+
+    pedersen_hash::<E, _>(
+        Personalization::MerkleTree(depth),
+        lhs.into_iter()
+           .take(E::Fr::NUM_BITS as usize)
+           .chain(rhs.into_iter().take(E::Fr::NUM_BITS as usize)),
+        params
+    ).into_xy().0
+}
+
+/// The authentication path of a note commitment, which proves that it is
+/// in the tree with a particular root.
+#[derive(Clone, Debug)]
+pub struct CommitmentTreeWitness<E: JubjubEngine> {
+    /// The sibling at each level, starting from the leaves, and whether the
+    /// path goes through the right child at that level.
+    pub auth_path: Vec<(E::Fr, bool)>,
+    /// The position of the commitment in the tree.
+    pub position: u64
+}
+
+impl<E: JubjubEngine> PartialEq for CommitmentTreeWitness<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.auth_path == other.auth_path && self.position == other.position
+    }
+}
+
+impl<E: JubjubEngine> CommitmentTreeWitness<E> {
+    /// Parses a witness for a tree of the given depth, in the format zcashd
+    /// passes to `librustzcash_sapling_spend_proof`:
+    ///
+    /// - the depth, as a single byte;
+    /// - for each level from the root down to the leaves, the byte 32
+    ///   followed by the sibling node in little-endian order;
+    /// - the position, as a little-endian u64.
+    ///
+    /// Returns an error if the witness is malformed, has trailing data, has
+    /// a sibling that is not a field element, or has a position that does
+    /// not fit in the tree.
+    pub fn from_slice(witness: &[u8], depth: usize) -> Result<Self, ()> {
+        if witness.len() != 1 + 33 * depth + 8 || witness[0] as usize != depth {
+            return Err(());
+        }
+        let mut witness = &witness[1..];
+
+        // The siblings are given starting from the root
+        let mut siblings = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            // The length of a Pedersen hash
+            if witness[0] != 32 {
+                return Err(());
+            }
+
+            let mut repr = <E::Fr as PrimeField>::Repr::default();
+            repr.read_le(&witness[1..33]).map_err(|_| ())?;
+            siblings.push(E::Fr::from_repr(repr).map_err(|_| ())?);
+
+            witness = &witness[33..];
+        }
+
+        let mut position = 0u64;
+        for (i, b) in witness.iter().enumerate() {
+            position |= (*b as u64) << (8 * i);
+        }
+        if depth < 64 && position >> depth != 0 {
+            return Err(());
+        }
+
+        let auth_path = siblings.into_iter()
+                                .rev()
+                                .enumerate()
+                                .map(|(i, sibling)| (sibling, (position >> i) & 1 == 1))
+                                .collect();
+
+        Ok(CommitmentTreeWitness {
+            auth_path: auth_path,
+            position: position
+        })
+    }
+
+    /// Computes the root of the tree from the note commitment `cm` at the
+    /// start of this path.
+    pub fn root(&self, cm: &E::Fr, params: &E::Params) -> E::Fr {
+        let mut cur = *cm;
+
+        for (i, &(ref sibling, is_right)) in self.auth_path.iter().enumerate() {
+            cur = if is_right {
+                merkle_hash::<E>(i, sibling, &cur, params)
+            } else {
+                merkle_hash::<E>(i, &cur, sibling, params)
+            };
+        }
+
+        cur
+    }
+
+    /// Returns the authentication path in the form taken by the Spend circuit.
+    pub fn circuit_auth_path(&self) -> Vec<Option<(E::Fr, bool)>> {
+        self.auth_path.iter().map(|node| Some(*node)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pairing::bls12_381::{Bls12, Fr};
+    use pairing::{PrimeField, PrimeFieldRepr};
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    use jubjub::JubjubBls12;
+
+    use super::{merkle_hash, CommitmentTreeWitness};
+
+    fn serialize(witness: &CommitmentTreeWitness<Bls12>) -> Vec<u8> {
+        let mut result = vec![witness.auth_path.len() as u8];
+        for &(ref sibling, _) in witness.auth_path.iter().rev() {
+            result.push(32);
+            sibling.into_repr().write_le(&mut result).unwrap();
+        }
+        for i in 0..8 {
+            result.push((witness.position >> (8 * i)) as u8);
+        }
+        result
+    }
+
+    #[test]
+    fn witness_from_slice() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let depth = 4;
+
+        let witness = CommitmentTreeWitness::<Bls12> {
+            auth_path: (0..depth).map(|i| (rng.gen(), i == 1 || i == 3)).collect(),
+            position: 0b1010
+        };
+        let encoded = serialize(&witness);
+        assert_eq!(encoded.len(), 1 + 33 * depth + 8);
+
+        let parsed = CommitmentTreeWitness::<Bls12>::from_slice(&encoded, depth).unwrap();
+        assert_eq!(parsed, witness);
+
+        // Wrong depth
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&encoded, depth + 1).is_err());
+        let mut bad = encoded.clone();
+        bad[0] = 5;
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&bad, depth).is_err());
+
+        // Trailing data
+        let mut bad = encoded.clone();
+        bad.push(0);
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&bad, depth).is_err());
+
+        // Bad sibling length prefix
+        let mut bad = encoded.clone();
+        bad[1] = 31;
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&bad, depth).is_err());
+
+        // Sibling out of the field
+        let mut bad = encoded.clone();
+        for b in &mut bad[2..34] {
+            *b = 0xff;
+        }
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&bad, depth).is_err());
+
+        // Position too large for the tree
+        let mut bad = encoded.clone();
+        bad[1 + 33 * depth] = 0b1_0000;
+        assert!(CommitmentTreeWitness::<Bls12>::from_slice(&bad, depth).is_err());
+    }
+
+    #[test]
+    fn witness_root() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+
+        // A tree of depth 2, built by hand
+        let leaves: Vec<Fr> = (0..4).map(|_| rng.gen()).collect();
+        let left = merkle_hash::<Bls12>(0, &leaves[0], &leaves[1], params);
+        let right = merkle_hash::<Bls12>(0, &leaves[2], &leaves[3], params);
+        let root = merkle_hash::<Bls12>(1, &left, &right, params);
+
+        // zcashd's encoding lists the siblings from the root down
+        let encode = |siblings: &[&Fr], position: u8| {
+            let mut result = vec![2];
+            for sibling in siblings {
+                result.push(32);
+                sibling.into_repr().write_le(&mut result).unwrap();
+            }
+            result.extend_from_slice(&[position, 0, 0, 0, 0, 0, 0, 0]);
+            result
+        };
+
+        let witnesses = [
+            (0, encode(&[&right, &leaves[1]], 0)),
+            (1, encode(&[&right, &leaves[0]], 1)),
+            (2, encode(&[&left, &leaves[3]], 2)),
+            (3, encode(&[&left, &leaves[2]], 3)),
+        ];
+        for &(position, ref encoded) in witnesses.iter() {
+            let witness = CommitmentTreeWitness::<Bls12>::from_slice(encoded, 2).unwrap();
+            assert_eq!(witness.position, position as u64);
+            assert_eq!(witness.root(&leaves[position], params), root);
+
+            // A different leaf at the same position gives a different root
+            let other = position ^ 1;
+            assert!(witness.root(&leaves[other], params) != root);
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//! End-to-end tests of two-party multisig spends. The co-signers aggregate
+//! their keys, run the randomizer and nonce rounds with every message sent
+//! through its wire encoding, and combine their partial signatures into a
+//! spend authorization signature that is checked with `PublicKey::verify`.
+
+use pairing::bls12_381::Bls12;
+use pairing::Field;
+use rand::{Rng, SeedableRng, XorShiftRng};
+
+use jubjub::{
+    edwards::Point, FixedGenerators, JubjubBls12, JubjubEngine, JubjubParams, PrimeOrder,
+};
+use musig::{
+    aggregate, aggregate_keys, aggregate_nonces, challenge, MusigCommitmentMsg, MusigError,
+    MusigNonceMsg, MusigPartialSigMsg, MusigParticipant, RandomizerContribution, SigningSession,
+};
+use primitives::{Diversifier, ProofGenerationKey};
+use redjubjub::{PrivateKey, PublicKey, Signature};
+
+type Fs = <Bls12 as JubjubEngine>::Fs;
+
+const SEED: [u32; 4] = [0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654];
+
+fn keygen(rng: &mut XorShiftRng, params: &JubjubBls12) -> (Fs, Point<Bls12, PrimeOrder>) {
+    let ask: Fs = rng.gen();
+    let ak = params
+        .generator(FixedGenerators::SpendingKeyGenerator)
+        .mul(ask, params);
+    (ask, ak)
+}
+
+/// Creates a co-signer for each `ask`, in the order of their indices.
+fn participants(asks: &[Fs], params: &JubjubBls12) -> Vec<MusigParticipant<Bls12>> {
+    let p_g = FixedGenerators::SpendingKeyGenerator;
+    let keys: Vec<_> = asks
+        .iter()
+        .map(|ask| params.generator(p_g).mul(*ask, params))
+        .collect();
+
+    let mut participants: Vec<_> = asks
+        .iter()
+        .map(|ask| MusigParticipant::new(*ask, &keys, params).unwrap())
+        .collect();
+    participants.sort_by_key(|p| p.index());
+    participants
+}
+
+/// Runs the randomizer round, returning the `ar` and `rk` that every
+/// co-signer agreed on.
+fn randomize(
+    participants: &[MusigParticipant<Bls12>],
+    rng: &mut XorShiftRng,
+    params: &JubjubBls12,
+) -> (Fs, PublicKey<Bls12>) {
+    let contributions: Vec<_> = participants
+        .iter()
+        .map(|_| RandomizerContribution::generate(rng))
+        .collect();
+    let commitments: Vec<_> = contributions.iter().map(|c| c.commit()).collect();
+
+    let results: Vec<_> = participants
+        .iter()
+        .map(|p| p.randomize(&commitments, &contributions, params).unwrap())
+        .collect();
+    for &(ar, ref rk) in &results[1..] {
+        assert_eq!(ar, results[0].0);
+        assert!(rk.0 == (results[0].1).0);
+    }
+    results.into_iter().next().unwrap()
+}
+
+/// Runs a signing session for `sighash` between all of the co-signers,
+/// sending each round's messages through their wire encoding.
+fn sign(
+    participants: &[MusigParticipant<Bls12>],
+    session_id: u64,
+    ar: Fs,
+    rk: &PublicKey<Bls12>,
+    sighash: &[u8; 32],
+    rng: &mut XorShiftRng,
+    params: &JubjubBls12,
+) -> Signature {
+    let sessions: Vec<_> = participants
+        .iter()
+        .map(|p| p.start_session(session_id, sighash, rng, params))
+        .collect();
+
+    // First half of the nonce round: the commitments
+    let commitments: Vec<_> = participants
+        .iter()
+        .zip(sessions.iter())
+        .map(|(p, s)| {
+            let mut encoded = vec![];
+            MusigCommitmentMsg {
+                signer: p.index() as u32,
+                commitment: s.nonce_commitment(),
+            }
+            .write(&mut encoded)
+            .unwrap();
+            let msg = MusigCommitmentMsg::read(&encoded[..]).unwrap();
+            assert_eq!(msg.signer, p.index() as u32);
+            msg.commitment
+        })
+        .collect();
+
+    // Second half: the nonces
+    let nonces: Vec<_> = participants
+        .iter()
+        .zip(sessions.iter())
+        .map(|(p, s)| {
+            let mut encoded = vec![];
+            MusigNonceMsg {
+                signer: p.index() as u32,
+                nonce: s.public_nonce().clone(),
+            }
+            .write(&mut encoded)
+            .unwrap();
+            MusigNonceMsg::<Bls12>::read(&encoded[..], params)
+                .unwrap()
+                .nonce
+        })
+        .collect();
+    let joint_nonce = aggregate_nonces(&commitments, &nonces, params).unwrap();
+
+    let partials: Vec<_> = participants
+        .iter()
+        .zip(sessions.into_iter())
+        .map(|(p, s)| {
+            let mut encoded = vec![];
+            MusigPartialSigMsg {
+                signer: p.index() as u32,
+                partial: s.partial_sign(&joint_nonce, rk, sighash),
+            }
+            .write(&mut encoded)
+            .unwrap();
+            MusigPartialSigMsg::<Bls12>::read(&encoded[..])
+                .unwrap()
+                .partial
+        })
+        .collect();
+
+    aggregate(&joint_nonce, rk, ar, sighash, &partials)
+}
+
+fn verify(
+    rk: &PublicKey<Bls12>,
+    sighash: &[u8; 32],
+    sig: &Signature,
+    params: &JubjubBls12,
+) -> bool {
+    let mut msg = [0u8; 64];
+    rk.write(&mut msg[..32]).unwrap();
+    msg[32..].copy_from_slice(sighash);
+    rk.verify(&msg, sig, FixedGenerators::SpendingKeyGenerator, params)
+}
+
+#[test]
+fn musig_addr_match() {
+    let rng = &mut XorShiftRng::from_seed(SEED);
+    let params = &JubjubBls12::new();
+
+    let (ask_1, ak_1) = keygen(rng, params);
+    let (ask_2, ak_2) = keygen(rng, params);
+    let participants = participants(&[ask_1, ask_2], params);
+    let ak = aggregate_keys(&[ak_1.clone(), ak_2.clone()], params).unwrap();
+    assert!(*participants[0].ak() == ak);
+    assert!(*participants[1].ak() == ak);
+
+    // Both co-signers share nk, and derive the same multisig address from
+    // their own key and the other's
+    let nsk = rng.gen();
+    let vk_1 = ProofGenerationKey {
+        ak: ak_1.clone(),
+        nsk,
+    }
+    .into_viewing_key(params);
+    let vk_2 = ProofGenerationKey {
+        ak: ak_2.clone(),
+        nsk,
+    }
+    .into_viewing_key(params);
+    let joint_vk = vk_1.make_multisig_with(ak_2.clone(), params).unwrap();
+    assert!(joint_vk.ak == ak);
+
+    let (diversifier, address) = loop {
+        let diversifier = Diversifier(rng.gen());
+        if let Some(address) = joint_vk.into_payment_address(diversifier, params) {
+            break (diversifier, address);
+        }
+    };
+    let address_1 = vk_1
+        .make_multisig_address_with(ak_2, diversifier, params)
+        .unwrap()
+        .unwrap();
+    let address_2 = vk_2
+        .make_multisig_address_with(ak_1, diversifier, params)
+        .unwrap()
+        .unwrap();
+    assert!(address_1.pk_d == address.pk_d);
+    assert!(address_2.pk_d == address.pk_d);
+
+    // A spend from the address is signed under the rk of the joint key
+    let (ar, rk) = randomize(&participants, rng, params);
+    assert!(rk.0 == joint_vk.rk(ar, params).into());
+
+    let sighash = [7u8; 32];
+    let sig = sign(&participants, 0, ar, &rk, &sighash, rng, params);
+    assert!(verify(&rk, &sighash, &sig, params));
+    assert!(!verify(&rk, &[8u8; 32], &sig, params));
+
+    // A co-signer cannot sign alone with its own key
+    let mut ask_ar = ask_1;
+    ask_ar.add_assign(&ar);
+    let msg = {
+        let mut msg = [0u8; 64];
+        rk.write(&mut msg[..32]).unwrap();
+        msg[32..].copy_from_slice(&sighash);
+        msg
+    };
+    let sig =
+        PrivateKey::<Bls12>(ask_ar).sign(&msg, rng, FixedGenerators::SpendingKeyGenerator, params);
+    assert!(!verify(&rk, &sighash, &sig, params));
+}
+
+#[test]
+fn rogue_key() {
+    let rng = &mut XorShiftRng::from_seed(SEED);
+    let params = &JubjubBls12::new();
+    let p_g = FixedGenerators::SpendingKeyGenerator;
+
+    // The attacker knows x, and publishes ak_2 = [x] P_G - ak_1 so that the
+    // plain sum of the keys is [x] P_G
+    let (_, ak_1) = keygen(rng, params);
+    let (x, x_g) = keygen(rng, params);
+    let ak_2 = x_g.add(&ak_1.negate(), params);
+    let sum = ak_1.add(&ak_2, params);
+    assert!(sum == x_g);
+
+    // The delinearized joint key is not [x] P_G
+    let ak = aggregate_keys(&[ak_1, ak_2], params).unwrap();
+    assert!(ak != x_g);
+
+    // So the attacker's signature, which would verify under the plain sum,
+    // does not verify under the joint key
+    let ar: Fs = rng.gen();
+    let sighash = [7u8; 32];
+    let sign_alone = |rk: &PublicKey<Bls12>, rng: &mut XorShiftRng| {
+        let mut msg = [0u8; 64];
+        rk.write(&mut msg[..32]).unwrap();
+        msg[32..].copy_from_slice(&sighash);
+        PrivateKey::<Bls12>(x)
+            .randomize(ar)
+            .sign(&msg, rng, p_g, params)
+    };
+
+    let rk_sum = PublicKey::<Bls12>(sum.into()).randomize(ar, p_g, params);
+    let sig = sign_alone(&rk_sum, rng);
+    assert!(verify(&rk_sum, &sighash, &sig, params));
+
+    let rk = PublicKey::<Bls12>(ak.into()).randomize(ar, p_g, params);
+    let sig = sign_alone(&rk, rng);
+    assert!(!verify(&rk, &sighash, &sig, params));
+
+    // A rogue key equal to the victim's, or the identity, is rejected
+    assert_eq!(
+        aggregate_keys(&[x_g.clone(), x_g.clone()], params).err(),
+        Some(MusigError::DuplicateKey)
+    );
+    assert_eq!(
+        aggregate_keys(&[x_g, Point::zero()], params).err(),
+        Some(MusigError::InvalidKey)
+    );
+}
+
+#[test]
+fn nonce_reuse() {
+    let rng = &mut XorShiftRng::from_seed(SEED);
+    let params = &JubjubBls12::new();
+
+    let (ask_1, _) = keygen(rng, params);
+    let (ask_2, _) = keygen(rng, params);
+    let participants = participants(&[ask_1, ask_2], params);
+    let sighash = [7u8; 32];
+
+    // A co-signer that reveals a different nonce from the one it committed
+    // to, for example one from an earlier session, is caught
+    let sessions: Vec<_> = participants
+        .iter()
+        .map(|p| p.start_session(1, &sighash, rng, params))
+        .collect();
+    let earlier = participants[1].start_session(2, &sighash, rng, params);
+    let commitments: Vec<_> = sessions.iter().map(|s| s.nonce_commitment()).collect();
+    assert_eq!(
+        aggregate_nonces(
+            &commitments,
+            &[
+                sessions[0].public_nonce().clone(),
+                earlier.public_nonce().clone()
+            ],
+            params
+        )
+        .err(),
+        Some(MusigError::InvalidNonce(1))
+    );
+
+    // Each session gets a fresh nonce, even for the same co-signer and
+    // sighash
+    let first = participants[0].start_session(3, &sighash, rng, params);
+    let second = participants[0].start_session(4, &sighash, rng, params);
+    assert!(first.nonce_commitment() != second.nonce_commitment());
+}
+
+/// Runs the nonce round between co-signer 1's `session` and co-signer 2's
+/// `other`, and returns the challenge and co-signer 1's partial signature.
+fn challenge_and_partial(
+    session: SigningSession<Bls12>,
+    other: &SigningSession<Bls12>,
+    rk: &PublicKey<Bls12>,
+    sighash: &[u8; 32],
+    params: &JubjubBls12,
+) -> (Fs, Fs) {
+    let commitments = [session.nonce_commitment(), other.nonce_commitment()];
+    let nonces = [session.public_nonce().clone(), other.public_nonce().clone()];
+    let joint_nonce = aggregate_nonces(&commitments, &nonces, params).unwrap();
+    let (_, c) = challenge(&joint_nonce, rk, sighash);
+    (c, session.partial_sign(&joint_nonce, rk, sighash).0)
+}
+
+/// Signs `sighash` with `share` in place of co-signer 1's weighted key
+/// share, together with an honest session of co-signer 2.
+fn sign_with_share(
+    share: Fs,
+    participants: &[MusigParticipant<Bls12>],
+    ar: Fs,
+    rk: &PublicKey<Bls12>,
+    sighash: &[u8; 32],
+    rng: &mut XorShiftRng,
+    params: &JubjubBls12,
+) -> Signature {
+    let sessions = vec![
+        SigningSession::new(share, 0, sighash, rng, params),
+        participants[1].start_session(100, sighash, rng, params),
+    ];
+    let commitments: Vec<_> = sessions.iter().map(|s| s.nonce_commitment()).collect();
+    let nonces: Vec<_> = sessions.iter().map(|s| s.public_nonce().clone()).collect();
+    let joint_nonce = aggregate_nonces(&commitments, &nonces, params).unwrap();
+    let partials: Vec<_> = sessions
+        .into_iter()
+        .map(|s| s.partial_sign(&joint_nonce, rk, sighash))
+        .collect();
+    aggregate(&joint_nonce, rk, ar, sighash, &partials)
+}
+
+#[test]
+fn stuck_rng_key_recovery() {
+    let rng = &mut XorShiftRng::from_seed(SEED);
+    let params = &JubjubBls12::new();
+
+    let (ask_1, _) = keygen(rng, params);
+    let (ask_2, _) = keygen(rng, params);
+    let participants = participants(&[ask_1, ask_2], params);
+    let (ar, rk) = randomize(&participants, rng, params);
+    let sighash = [7u8; 32];
+
+    // Co-signer 1's RNG is stuck, so its nonce depends only on its key, the
+    // session id and the message. Co-signer 2 (the attacker) uses a new
+    // nonce each time, so the two sessions have different joint nonces and
+    // challenges, and partial signatures s = r + c . w with the same r
+    // reveal co-signer 1's weighted key share w = (s - s') / (c - c').
+    let stuck_session = |session_id: u64| {
+        let stuck_rng = &mut XorShiftRng::from_seed([1, 2, 3, 4]);
+        participants[0].start_session(session_id, &sighash, stuck_rng, params)
+    };
+    let recover = |session_ids: (u64, u64), rng: &mut XorShiftRng| {
+        let (session, session_again) = (stuck_session(session_ids.0), stuck_session(session_ids.1));
+        let reused = session.nonce_commitment() == session_again.nonce_commitment();
+
+        let other = participants[1].start_session(10, &sighash, rng, params);
+        let (c, s) = challenge_and_partial(session, &other, &rk, &sighash, params);
+        let other = participants[1].start_session(11, &sighash, rng, params);
+        let (c_again, s_again) =
+            challenge_and_partial(session_again, &other, &rk, &sighash, params);
+
+        let mut c_diff = c;
+        c_diff.sub_assign(&c_again);
+        let mut w = s;
+        w.sub_assign(&s_again);
+        w.mul_assign(&c_diff.inverse().unwrap());
+        (reused, w)
+    };
+
+    // The recovered share lets the attacker sign any message without
+    // co-signer 1
+    let (reused, w) = recover((1, 1), rng);
+    assert!(reused);
+    let other_sighash = [8u8; 32];
+    let sig = sign_with_share(w, &participants, ar, &rk, &other_sighash, rng, params);
+    assert!(verify(&rk, &other_sighash, &sig, params));
+
+    // A session id that is never reused gives each session its own nonce,
+    // even with the stuck RNG, and nothing is recovered
+    let (reused, w) = recover((2, 3), rng);
+    assert!(!reused);
+    let sig = sign_with_share(w, &participants, ar, &rk, &other_sighash, rng, params);
+    assert!(!verify(&rk, &other_sighash, &sig, params));
+}
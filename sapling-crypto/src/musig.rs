@@ -0,0 +1,913 @@
+//! MuSig signing of RedJubjub spend authorization signatures, for notes
+//! whose spend authorizing key is shared between several co-signers.
+//!
+//! Each signing session starts with a nonce round. Every co-signer
+//! generates a nonce and first sends only a commitment to its public part.
+//! Once it has received everyone else's commitment, it reveals the public
+//! nonce itself. Each revealed nonce is checked against its commitment
+//! before the nonces are added to get the joint nonce `R`. The commitments
+//! stop the last co-signer from choosing its nonce based on the others'.
+//!
+//! The joint spend validating key is `ak = sum(a_i . ak_i)` over the
+//! co-signers' keys `ak_i`. Each delinearization factor `a_i` is a hash of
+//! the whole sorted key set and of `ak_i`, so that no co-signer can choose
+//! its key to cancel out the others' (a rogue-key attack). A co-signer is
+//! represented by a `MusigParticipant`, which knows the key set.
+//!
+//! A co-signer's part in a session is held by a `SigningSession`, which
+//! a `MusigParticipant` starts with a fresh nonce. After the nonce round,
+//! each co-signer computes a partial signature `s_i = r_i + c . a_i . ask_i`
+//! with the challenge `c = H*(Rbar || M)`. The partial signatures are added, along
+//! with `c . ar` for the randomizer `ar` of `rk`, to give a RedJubjub
+//! signature that verifies under `rk`.
+//!
+//! A session's nonce is derived from fresh randomness, the co-signer's key
+//! share, a session id, the key set and the sighash being signed, as in
+//! `PrivateKey::sign`. A nonce is never safe to reuse, even for the same
+//! message, because the joint nonce (and so the challenge) also depends on
+//! the other co-signers' nonces. Two partial signatures with the same nonce
+//! under different joint nonces reveal the co-signer's weighted key share.
+//!
+//! If the RNG is broken, restarting a session for the same message would
+//! derive the same nonce again. The session id is what keeps the nonces of
+//! such sessions apart, so the caller must never use the same session id
+//! twice with the same key, including across restarts of the process. A
+//! counter that is persisted before each session starts is enough. An
+//! abandoned session must be restarted with a new session id, never
+//! resumed.
+//!
+//! The secret scalars held by participants, sessions and nonces are
+//! overwritten with zero when they are dropped, whether the session
+//! finished or was abandoned.
+//!
+//! The randomizer `ar` of `rk` must be fixed before the sighash, which
+//! commits to `rk`. The co-signers agree on it in a round of its own, which
+//! is run like the nonce round: each co-signer commits to a random
+//! `RandomizerContribution`, then reveals it, and `ar` is a hash of `ak`
+//! and all of the contributions. It is unpredictable as long as one
+//! co-signer is honest, so `rk` cannot be linked to `ak`.
+//!
+//! The messages that co-signers send each other in each round have a fixed
+//! encoding: the sender's index as a little-endian `u32`, then the 32-byte
+//! encoding of the commitment, nonce or partial signature.
+
+use blake2_rfc::blake2s::Blake2s;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use pairing::Field;
+use rand::Rng;
+use std::io::{self, Read, Write};
+
+use jubjub::{
+    edwards::Point, FixedGenerators, JubjubEngine, JubjubParams, PrimeOrder, Unknown, Zeroize,
+};
+use redjubjub::{h_star, read_scalar, synthetic_nonce, write_scalar, PublicKey, Signature};
+use util::hash_to_scalar;
+
+/// BLAKE2s personalization for commitments to public nonces. This is not
+/// part of the Sapling protocol.
+const NONCE_COMMITMENT_PERSONALIZATION: &'static [u8; 8] = b"MuSig_Rc";
+
+/// BLAKE2b personalization for the delinearization factors. This is not
+/// part of the Sapling protocol.
+const KEY_AGGREGATION_PERSONALIZATION: &'static [u8; 16] = b"MuSig_KeyAggCoef";
+
+/// BLAKE2s personalization for commitments to randomizer contributions.
+/// This is not part of the Sapling protocol.
+const RANDOMIZER_COMMITMENT_PERSONALIZATION: &'static [u8; 8] = b"MuSig_Ac";
+
+/// BLAKE2b personalization for the joint randomizer `ar`. This is not part
+/// of the Sapling protocol.
+const RANDOMIZER_PERSONALIZATION: &'static [u8; 16] = b"MuSig_Randomizer";
+
+/// The errors that can occur while aggregating keys or running a MuSig
+/// session.
+#[derive(Debug, PartialEq)]
+pub enum MusigError {
+    /// The key set was empty.
+    NoKeys,
+    /// The same key appeared twice in the key set.
+    DuplicateKey,
+    /// A key in the key set was the identity.
+    InvalidKey,
+    /// The co-signer's own key was not in the key set.
+    MissingOwnKey,
+    /// The number of nonces did not match the number of commitments.
+    WrongNumberOfNonces,
+    /// The nonce at this index did not match its commitment, or was of
+    /// small order.
+    InvalidNonce(usize),
+    /// The number of randomizer contributions did not match the number of
+    /// commitments.
+    WrongNumberOfContributions,
+    /// The randomizer contribution at this index did not match its
+    /// commitment.
+    InvalidContribution(usize),
+}
+
+/// A co-signer's secret nonce `r`. It must be used for only one signature.
+pub struct SecretNonce<E: JubjubEngine>(pub(crate) E::Fs);
+
+impl<E: JubjubEngine> Drop for SecretNonce<E> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// A co-signer's public nonce `R = [r] P_G`.
+#[derive(Clone)]
+pub struct PublicNonce<E: JubjubEngine>(pub Point<E, Unknown>);
+
+/// A co-signer's share `s_i` of a signature.
+pub struct PartialSignature<E: JubjubEngine>(pub E::Fs);
+
+impl<E: JubjubEngine> Clone for PartialSignature<E> {
+    fn clone(&self) -> Self {
+        PartialSignature(self.0)
+    }
+}
+
+impl<E: JubjubEngine> Copy for PartialSignature<E> {}
+
+/// A commitment to a public nonce, sent before the nonce is revealed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonceCommitment(pub [u8; 32]);
+
+impl<E: JubjubEngine> PublicNonce<E> {
+    /// Returns the commitment to this nonce that is sent in the first half
+    /// of the nonce round.
+    pub fn commit(&self) -> NonceCommitment {
+        let mut rbar = [0u8; 32];
+        self.0
+            .write(&mut rbar[..])
+            .expect("Jubjub points should serialize to 32 bytes");
+
+        let mut h = Blake2s::with_params(32, &[], &[], NONCE_COMMITMENT_PERSONALIZATION);
+        h.update(&rbar);
+
+        let mut commitment = [0u8; 32];
+        commitment.copy_from_slice(h.finalize().as_ref());
+        NonceCommitment(commitment)
+    }
+}
+
+impl NonceCommitment {
+    /// Returns true if `nonce` opens this commitment and is not of small
+    /// order.
+    pub fn verify<E: JubjubEngine>(&self, nonce: &PublicNonce<E>, params: &E::Params) -> bool {
+        nonce.commit() == *self && nonce.0.mul_by_cofactor(params) != Point::zero()
+    }
+}
+
+/// A co-signer's random contribution to the randomizer `ar`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomizerContribution(pub [u8; 32]);
+
+/// A commitment to a randomizer contribution, sent before the contribution
+/// is revealed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomizerCommitment(pub [u8; 32]);
+
+impl RandomizerContribution {
+    /// Generates a fresh contribution.
+    pub fn generate<R: Rng>(rng: &mut R) -> Self {
+        RandomizerContribution(rng.gen())
+    }
+
+    /// Returns the commitment to this contribution that is sent in the first
+    /// half of the randomizer round.
+    pub fn commit(&self) -> RandomizerCommitment {
+        let mut h = Blake2s::with_params(32, &[], &[], RANDOMIZER_COMMITMENT_PERSONALIZATION);
+        h.update(&self.0);
+
+        let mut commitment = [0u8; 32];
+        commitment.copy_from_slice(h.finalize().as_ref());
+        RandomizerCommitment(commitment)
+    }
+}
+
+impl RandomizerCommitment {
+    /// Returns true if `contribution` opens this commitment.
+    pub fn verify(&self, contribution: &RandomizerContribution) -> bool {
+        contribution.commit() == *self
+    }
+}
+
+/// Sorts the co-signers' keys by their encodings, rejecting an empty key
+/// set, the identity or a repeated key.
+fn sort_keys<E: JubjubEngine>(
+    keys: &[Point<E, PrimeOrder>],
+) -> Result<Vec<([u8; 32], Point<E, PrimeOrder>)>, MusigError> {
+    let mut sorted: Vec<_> = keys
+        .iter()
+        .map(|ak| {
+            let mut akbar = [0u8; 32];
+            ak.write(&mut akbar[..])
+                .expect("Jubjub points should serialize to 32 bytes");
+            (akbar, ak.clone())
+        })
+        .collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if sorted.is_empty() {
+        Err(MusigError::NoKeys)
+    } else if sorted.iter().any(|k| k.1 == Point::zero()) {
+        Err(MusigError::InvalidKey)
+    } else if sorted.windows(2).any(|w| w[0].0 == w[1].0) {
+        Err(MusigError::DuplicateKey)
+    } else {
+        Ok(sorted)
+    }
+}
+
+/// Returns the encoding `L` of the key set, which is the concatenation of
+/// the sorted keys.
+fn key_set_encoding<E: JubjubEngine>(sorted: &[([u8; 32], Point<E, PrimeOrder>)]) -> Vec<u8> {
+    sorted.iter().flat_map(|k| k.0.iter().cloned()).collect()
+}
+
+/// Returns the delinearization factor `a_i = H(L || ak_i)` for each key,
+/// in sorted order.
+fn delinearization_factors<E: JubjubEngine>(
+    sorted: &[([u8; 32], Point<E, PrimeOrder>)],
+) -> Vec<E::Fs> {
+    let l = key_set_encoding(sorted);
+
+    sorted
+        .iter()
+        .map(|k| hash_to_scalar::<E>(KEY_AGGREGATION_PERSONALIZATION, &l, &k.0))
+        .collect()
+}
+
+/// Returns the joint key `ak = sum(a_i . ak_i)` for the given key set. The
+/// order of `keys` does not matter.
+pub fn aggregate_keys<E: JubjubEngine>(
+    keys: &[Point<E, PrimeOrder>],
+    params: &E::Params,
+) -> Result<Point<E, PrimeOrder>, MusigError> {
+    let sorted = sort_keys(keys)?;
+    let factors = delinearization_factors::<E>(&sorted);
+
+    Ok(sorted
+        .iter()
+        .zip(factors.iter())
+        .fold(Point::zero(), |ak, (k, a)| {
+            ak.add(&k.1.mul(*a, params), params)
+        }))
+}
+
+/// A co-signer of an n-of-n spend authorizing key.
+pub struct MusigParticipant<E: JubjubEngine> {
+    ask: E::Fs,
+    index: usize,
+    factor: E::Fs,
+    num_signers: usize,
+    key_set: Vec<u8>,
+    ak: Point<E, PrimeOrder>,
+}
+
+impl<E: JubjubEngine> MusigParticipant<E> {
+    /// Creates the co-signer holding `ask`, among the co-signers with the
+    /// spend validating keys `keys`. The list must include this co-signer's
+    /// own key, and may be in any order.
+    pub fn new(
+        ask: E::Fs,
+        keys: &[Point<E, PrimeOrder>],
+        params: &E::Params,
+    ) -> Result<Self, MusigError> {
+        let sorted = sort_keys(keys)?;
+        let factors = delinearization_factors::<E>(&sorted);
+
+        let own_key = params
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .mul(ask, params);
+        let index = match sorted.iter().position(|k| k.1 == own_key) {
+            Some(index) => index,
+            None => return Err(MusigError::MissingOwnKey),
+        };
+
+        let ak = aggregate_keys(keys, params)?;
+
+        Ok(MusigParticipant {
+            ask,
+            index,
+            factor: factors[index],
+            num_signers: sorted.len(),
+            key_set: key_set_encoding(&sorted),
+            ak,
+        })
+    }
+
+    /// Returns this co-signer's position among the sorted keys. This is its
+    /// index in the messages of a signing session.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of co-signers.
+    pub fn num_signers(&self) -> usize {
+        self.num_signers
+    }
+
+    /// Returns the joint spend validating key `ak`.
+    pub fn ak(&self) -> &Point<E, PrimeOrder> {
+        &self.ak
+    }
+
+    /// Returns the joint randomizer `ar` and the randomized key `rk` for the
+    /// co-signers' revealed randomizer contributions. See
+    /// `aggregate_randomizers`.
+    pub fn randomize(
+        &self,
+        commitments: &[RandomizerCommitment],
+        contributions: &[RandomizerContribution],
+        params: &E::Params,
+    ) -> Result<(E::Fs, PublicKey<E>), MusigError> {
+        aggregate_randomizers(&self.ak, commitments, contributions, params)
+    }
+
+    /// Starts a session to sign `sighash`, with a fresh nonce.
+    ///
+    /// `session_id` must not have been used before with this co-signer's
+    /// key, even by an earlier run of the process. See the module
+    /// documentation.
+    pub fn start_session<R: Rng>(
+        &self,
+        session_id: u64,
+        sighash: &[u8; 32],
+        rng: &mut R,
+        params: &E::Params,
+    ) -> SigningSession<E> {
+        let mut ask = self.ask;
+        ask.mul_assign(&self.factor);
+
+        let mut context = self.key_set.clone();
+        context.extend_from_slice(sighash);
+        let session = SigningSession::new(ask, session_id, &context, rng, params);
+
+        // The session holds its own copy of the weighted share
+        ask.zeroize();
+
+        session
+    }
+}
+
+impl<E: JubjubEngine> Drop for MusigParticipant<E> {
+    fn drop(&mut self) {
+        self.ask.zeroize();
+    }
+}
+
+/// One co-signer's state in a signing session: its weighted share
+/// `a_i . ask_i` of the spend authorizing key, and the nonce it generated
+/// for this session.
+pub struct SigningSession<E: JubjubEngine> {
+    ask: E::Fs,
+    nonce: SecretNonce<E>,
+    public_nonce: PublicNonce<E>,
+}
+
+impl<E: JubjubEngine> SigningSession<E> {
+    /// Starts a session for the weighted key share `ask`, with a nonce over
+    /// the spend authorization generator derived from fresh randomness,
+    /// `ask`, `session_id` and `context`. The context must include the
+    /// sighash.
+    pub(crate) fn new<R: Rng>(
+        ask: E::Fs,
+        session_id: u64,
+        context: &[u8],
+        rng: &mut R,
+        params: &E::Params,
+    ) -> Self {
+        let mut input = vec![];
+        input
+            .write_u64::<LittleEndian>(session_id)
+            .expect("writing to a Vec should not fail");
+        input.extend_from_slice(context);
+
+        let r = synthetic_nonce::<E, R>(&ask, &input, rng);
+        let r_g = params
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .mul(r, params)
+            .into();
+
+        SigningSession {
+            ask,
+            nonce: SecretNonce(r),
+            public_nonce: PublicNonce(r_g),
+        }
+    }
+
+    /// Returns the commitment to send in the first half of the nonce round.
+    pub fn nonce_commitment(&self) -> NonceCommitment {
+        self.public_nonce.commit()
+    }
+
+    /// Returns the nonce to reveal once every other co-signer's commitment
+    /// has been received.
+    pub fn public_nonce(&self) -> &PublicNonce<E> {
+        &self.public_nonce
+    }
+
+    /// Computes this co-signer's partial signature on `sighash`, for the
+    /// joint nonce returned by `aggregate_nonces` and the randomized joint
+    /// key `rk`.
+    ///
+    /// The session is consumed, so that its nonce is never used for a
+    /// second signature.
+    pub fn partial_sign(
+        self,
+        joint_nonce: &PublicNonce<E>,
+        rk: &PublicKey<E>,
+        sighash: &[u8; 32],
+    ) -> PartialSignature<E> {
+        let (_, mut s) = challenge(joint_nonce, rk, sighash);
+        s.mul_assign(&self.ask);
+        s.add_assign(&self.nonce.0);
+        PartialSignature(s)
+    }
+}
+
+impl<E: JubjubEngine> Drop for SigningSession<E> {
+    fn drop(&mut self) {
+        self.ask.zeroize();
+    }
+}
+
+/// Returns `Rbar` and the challenge `c = H*(Rbar || M)`, where the message
+/// `M = rk || sighash` is the one signed by a spend authorization signature.
+pub(crate) fn challenge<E: JubjubEngine>(
+    joint_nonce: &PublicNonce<E>,
+    rk: &PublicKey<E>,
+    sighash: &[u8; 32],
+) -> ([u8; 32], E::Fs) {
+    let mut rbar = [0u8; 32];
+    joint_nonce
+        .0
+        .write(&mut rbar[..])
+        .expect("Jubjub points should serialize to 32 bytes");
+
+    let mut msg = [0u8; 64];
+    rk.write(&mut msg[..32])
+        .expect("Jubjub points should serialize to 32 bytes");
+    msg[32..].copy_from_slice(sighash);
+
+    (rbar, h_star::<E>(&rbar, &msg))
+}
+
+/// Combines the co-signers' partial signatures into a spend authorization
+/// signature on `sighash` that verifies under `rk = ak + [ar] P_G`.
+pub fn aggregate<E: JubjubEngine>(
+    joint_nonce: &PublicNonce<E>,
+    rk: &PublicKey<E>,
+    ar: E::Fs,
+    sighash: &[u8; 32],
+    partials: &[PartialSignature<E>],
+) -> Signature {
+    let (rbar, mut s) = challenge(joint_nonce, rk, sighash);
+    s.mul_assign(&ar);
+    for partial in partials {
+        s.add_assign(&partial.0);
+    }
+
+    let mut sbar = [0u8; 32];
+    write_scalar::<E, &mut [u8]>(&s, &mut sbar[..])
+        .expect("Jubjub scalars should serialize to 32 bytes");
+
+    Signature { rbar, sbar }
+}
+
+/// Checks each co-signer's revealed nonce against the commitment it sent
+/// earlier, and adds them to get the joint nonce `R`.
+///
+/// `commitments` and `nonces` must list the co-signers in the same order.
+pub fn aggregate_nonces<E: JubjubEngine>(
+    commitments: &[NonceCommitment],
+    nonces: &[PublicNonce<E>],
+    params: &E::Params,
+) -> Result<PublicNonce<E>, MusigError> {
+    if commitments.len() != nonces.len() || nonces.is_empty() {
+        return Err(MusigError::WrongNumberOfNonces);
+    }
+
+    let mut r = Point::zero();
+    for (i, (commitment, nonce)) in commitments.iter().zip(nonces.iter()).enumerate() {
+        if !commitment.verify(nonce, params) {
+            return Err(MusigError::InvalidNonce(i));
+        }
+        r = r.add(&nonce.0, params);
+    }
+
+    Ok(PublicNonce(r))
+}
+
+/// Checks each co-signer's revealed randomizer contribution against the
+/// commitment it sent earlier, and returns the joint randomizer
+/// `ar = H(ak || contributions)` with the randomized key `rk = ak + [ar] P_G`.
+///
+/// `commitments` and `contributions` must list the co-signers in the same
+/// order, which all co-signers must agree on.
+pub fn aggregate_randomizers<E: JubjubEngine>(
+    ak: &Point<E, PrimeOrder>,
+    commitments: &[RandomizerCommitment],
+    contributions: &[RandomizerContribution],
+    params: &E::Params,
+) -> Result<(E::Fs, PublicKey<E>), MusigError> {
+    if commitments.len() != contributions.len() || contributions.is_empty() {
+        return Err(MusigError::WrongNumberOfContributions);
+    }
+
+    let mut input = vec![];
+    for (i, (commitment, contribution)) in commitments.iter().zip(contributions.iter()).enumerate()
+    {
+        if !commitment.verify(contribution) {
+            return Err(MusigError::InvalidContribution(i));
+        }
+        input.extend_from_slice(&contribution.0);
+    }
+
+    let mut akbar = [0u8; 32];
+    ak.write(&mut akbar[..])
+        .expect("Jubjub points should serialize to 32 bytes");
+    let ar = hash_to_scalar::<E>(RANDOMIZER_PERSONALIZATION, &akbar, &input);
+
+    let rk =
+        PublicKey(ak.clone().into()).randomize(ar, FixedGenerators::SpendingKeyGenerator, params);
+    Ok((ar, rk))
+}
+
+/// The first-round message, carrying a co-signer's nonce commitment.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MusigCommitmentMsg {
+    pub signer: u32,
+    pub commitment: NonceCommitment,
+}
+
+impl MusigCommitmentMsg {
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let signer = reader.read_u32::<LittleEndian>()?;
+        let mut commitment = [0u8; 32];
+        reader.read_exact(&mut commitment)?;
+
+        Ok(MusigCommitmentMsg {
+            signer,
+            commitment: NonceCommitment(commitment),
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<LittleEndian>(self.signer)?;
+        writer.write_all(&self.commitment.0)
+    }
+}
+
+/// The second-round message, revealing a co-signer's public nonce.
+///
+/// Reading does not check the nonce. That happens when it is checked
+/// against its commitment in `aggregate_nonces`.
+#[derive(Clone)]
+pub struct MusigNonceMsg<E: JubjubEngine> {
+    pub signer: u32,
+    pub nonce: PublicNonce<E>,
+}
+
+impl<E: JubjubEngine> MusigNonceMsg<E> {
+    pub fn read<R: Read>(mut reader: R, params: &E::Params) -> io::Result<Self> {
+        let signer = reader.read_u32::<LittleEndian>()?;
+        let nonce = Point::read(reader, params)?;
+
+        Ok(MusigNonceMsg {
+            signer,
+            nonce: PublicNonce(nonce),
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<LittleEndian>(self.signer)?;
+        self.nonce.0.write(writer)
+    }
+}
+
+/// The final message, carrying a co-signer's partial signature.
+#[derive(Clone)]
+pub struct MusigPartialSigMsg<E: JubjubEngine> {
+    pub signer: u32,
+    pub partial: PartialSignature<E>,
+}
+
+impl<E: JubjubEngine> MusigPartialSigMsg<E> {
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let signer = reader.read_u32::<LittleEndian>()?;
+        let partial = read_scalar::<E, R>(reader)?;
+
+        Ok(MusigPartialSigMsg {
+            signer,
+            partial: PartialSignature(partial),
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<LittleEndian>(self.signer)?;
+        write_scalar::<E, W>(&self.partial.0, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pairing::bls12_381::Bls12;
+    use pairing::Field;
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    use jubjub::{edwards::Point, FixedGenerators, JubjubBls12, JubjubParams};
+
+    use super::*;
+
+    /// Generates a nonce straight from the RNG, for testing the nonce round
+    /// on its own. Sessions derive their nonces with `synthetic_nonce`.
+    fn generate_nonce<E: JubjubEngine, R: Rng>(
+        rng: &mut R,
+        p_g: FixedGenerators,
+        params: &E::Params,
+    ) -> (SecretNonce<E>, PublicNonce<E>) {
+        let r: E::Fs = rng.gen();
+        let r_g = params.generator(p_g).mul(r, params).into();
+
+        (SecretNonce(r), PublicNonce(r_g))
+    }
+
+    #[test]
+    fn nonce_round() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let (r1, nonce1) = generate_nonce::<Bls12, _>(rng, p_g, params);
+        let (r2, nonce2) = generate_nonce::<Bls12, _>(rng, p_g, params);
+
+        // Commitments are exchanged first, then the nonces
+        let commitments = [nonce1.commit(), nonce2.commit()];
+        let nonces = [nonce1.clone(), nonce2.clone()];
+
+        let joint = aggregate_nonces(&commitments, &nonces, params).unwrap();
+
+        let mut r = r1.0;
+        r.add_assign(&r2.0);
+        let expected: Point<Bls12, Unknown> = params.generator(p_g).mul(r, params).into();
+        assert!(joint.0 == expected);
+
+        // A nonce that was changed after committing is rejected
+        let (_, nonce3) = generate_nonce::<Bls12, _>(rng, p_g, params);
+        assert_eq!(
+            aggregate_nonces(&commitments, &[nonce1.clone(), nonce3], params).err(),
+            Some(MusigError::InvalidNonce(1))
+        );
+
+        // So is a small-order nonce, even with a matching commitment
+        let zero = PublicNonce::<Bls12>(Point::zero());
+        assert_eq!(
+            aggregate_nonces(&[commitments[0], zero.commit()], &[nonce1, zero], params).err(),
+            Some(MusigError::InvalidNonce(1))
+        );
+
+        assert_eq!(
+            aggregate_nonces(&commitments, &nonces[..1], params).err(),
+            Some(MusigError::WrongNumberOfNonces)
+        );
+    }
+
+    #[test]
+    fn session_nonce() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+
+        let ask: <Bls12 as JubjubEngine>::Fs = rng.gen();
+        let session = SigningSession::<Bls12>::new(ask, 0, &[1; 32], rng, params);
+
+        let expected: Point<Bls12, Unknown> = params
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .mul(session.nonce.0, params)
+            .into();
+        assert!(session.public_nonce().0 == expected);
+        assert!(session
+            .nonce_commitment()
+            .verify(session.public_nonce(), params));
+
+        // Each session gets its own nonce, even for the same context
+        let other = SigningSession::<Bls12>::new(ask, 0, &[1; 32], rng, params);
+        assert!(other.nonce_commitment() != session.nonce_commitment());
+
+        // With repeated randomness, the nonce still depends on the session
+        // id and the context
+        let seed = [0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654];
+        let same = |session_id: u64, context: &[u8]| {
+            let rng = &mut XorShiftRng::from_seed(seed);
+            SigningSession::<Bls12>::new(ask, session_id, context, rng, params).nonce_commitment()
+        };
+        assert_eq!(same(0, &[1; 32]), same(0, &[1; 32]));
+        assert!(same(0, &[1; 32]) != same(1, &[1; 32]));
+        assert!(same(0, &[1; 32]) != same(0, &[2; 32]));
+    }
+
+    #[test]
+    fn key_aggregation() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let asks: Vec<<Bls12 as JubjubEngine>::Fs> = (0..3).map(|_| rng.gen()).collect();
+        let keys: Vec<_> = asks
+            .iter()
+            .map(|ask| params.generator(p_g).mul(*ask, params))
+            .collect();
+
+        // The joint key does not depend on the order of the keys, and is not
+        // their plain sum
+        let ak = aggregate_keys::<Bls12>(&keys, params).unwrap();
+        let reversed: Vec<_> = keys.iter().rev().cloned().collect();
+        assert!(aggregate_keys::<Bls12>(&reversed, params).unwrap() == ak);
+        let sum = keys[1..]
+            .iter()
+            .fold(keys[0].clone(), |acc, k| acc.add(k, params));
+        assert!(ak != sum);
+
+        // Each participant gets a distinct index and the same joint key
+        let mut indices: Vec<_> = asks
+            .iter()
+            .map(|ask| {
+                let participant = MusigParticipant::<Bls12>::new(*ask, &keys, params).unwrap();
+                assert!(*participant.ak() == ak);
+                assert_eq!(participant.num_signers(), 3);
+                participant.index()
+            })
+            .collect();
+        indices.sort();
+        assert_eq!(indices, vec![0, 1, 2]);
+
+        assert_eq!(
+            aggregate_keys::<Bls12>(&[], params).err(),
+            Some(MusigError::NoKeys)
+        );
+        assert_eq!(
+            aggregate_keys::<Bls12>(&[keys[0].clone(), keys[0].clone()], params).err(),
+            Some(MusigError::DuplicateKey)
+        );
+        assert_eq!(
+            MusigParticipant::<Bls12>::new(asks[0], &keys[1..], params).err(),
+            Some(MusigError::MissingOwnKey)
+        );
+    }
+
+    #[test]
+    fn n_of_n_signature() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let asks: Vec<<Bls12 as JubjubEngine>::Fs> = (0..3).map(|_| rng.gen()).collect();
+        let keys: Vec<_> = asks
+            .iter()
+            .map(|ask| params.generator(p_g).mul(*ask, params))
+            .collect();
+        let participants: Vec<_> = asks
+            .iter()
+            .map(|ask| MusigParticipant::<Bls12>::new(*ask, &keys, params).unwrap())
+            .collect();
+
+        let ar = rng.gen();
+        let rk = PublicKey::<Bls12>(participants[0].ak().clone().into()).randomize(ar, p_g, params);
+        let sighash = [7u8; 32];
+
+        let sessions: Vec<_> = participants
+            .iter()
+            .map(|p| p.start_session(0, &sighash, rng, params))
+            .collect();
+        let commitments: Vec<_> = sessions.iter().map(|s| s.nonce_commitment()).collect();
+        let nonces: Vec<_> = sessions.iter().map(|s| s.public_nonce().clone()).collect();
+        let joint_nonce = aggregate_nonces(&commitments, &nonces, params).unwrap();
+
+        let partials: Vec<_> = sessions
+            .into_iter()
+            .map(|s| s.partial_sign(&joint_nonce, &rk, &sighash))
+            .collect();
+        let sig = aggregate(&joint_nonce, &rk, ar, &sighash, &partials);
+
+        let mut msg = [0u8; 64];
+        rk.write(&mut msg[..32]).unwrap();
+        msg[32..].copy_from_slice(&sighash);
+        assert!(rk.verify(&msg, &sig, p_g, params));
+
+        // A missing partial signature, or a different sighash, fails
+        let sig = aggregate(&joint_nonce, &rk, ar, &sighash, &partials[..2]);
+        assert!(!rk.verify(&msg, &sig, p_g, params));
+        let sig = aggregate(&joint_nonce, &rk, ar, &[8u8; 32], &partials);
+        assert!(!rk.verify(&msg, &sig, p_g, params));
+    }
+
+    #[test]
+    fn randomizer_round() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let asks: Vec<<Bls12 as JubjubEngine>::Fs> = (0..2).map(|_| rng.gen()).collect();
+        let keys: Vec<_> = asks
+            .iter()
+            .map(|ask| params.generator(p_g).mul(*ask, params))
+            .collect();
+        let participants: Vec<_> = asks
+            .iter()
+            .map(|ask| MusigParticipant::<Bls12>::new(*ask, &keys, params).unwrap())
+            .collect();
+
+        // Commitments are exchanged first, then the contributions
+        let contributions: Vec<_> = (0..2)
+            .map(|_| RandomizerContribution::generate(rng))
+            .collect();
+        let commitments: Vec<_> = contributions.iter().map(|c| c.commit()).collect();
+
+        // Every co-signer derives the same ar and rk, and rk = ak + [ar] P_G
+        let (ar, rk) = participants[0]
+            .randomize(&commitments, &contributions, params)
+            .unwrap();
+        let (ar_1, rk_1) = participants[1]
+            .randomize(&commitments, &contributions, params)
+            .unwrap();
+        assert_eq!(ar, ar_1);
+        assert!(rk.0 == rk_1.0);
+        let vk = ::primitives::ViewingKey {
+            ak: participants[0].ak().clone(),
+            nk: Point::zero(),
+        };
+        assert!(rk.0 == vk.rk(ar, params).into());
+
+        // A different contribution gives a different ar
+        let other = RandomizerContribution::generate(rng);
+        let (ar_2, _) = aggregate_randomizers(
+            participants[0].ak(),
+            &[commitments[0], other.commit()],
+            &[contributions[0], other],
+            params,
+        )
+        .unwrap();
+        assert!(ar_2 != ar);
+
+        // A contribution that was changed after committing is rejected
+        assert_eq!(
+            participants[0]
+                .randomize(&commitments, &[contributions[0], other], params)
+                .err(),
+            Some(MusigError::InvalidContribution(1))
+        );
+        assert_eq!(
+            participants[0]
+                .randomize(&commitments, &contributions[..1], params)
+                .err(),
+            Some(MusigError::WrongNumberOfContributions)
+        );
+    }
+
+    #[test]
+    fn message_round_trip() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let (_, nonce) = generate_nonce::<Bls12, _>(rng, p_g, params);
+
+        let msg = MusigCommitmentMsg {
+            signer: 2,
+            commitment: nonce.commit(),
+        };
+        let mut encoded = vec![];
+        msg.write(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), 36);
+        assert_eq!(&encoded[..4], &[2, 0, 0, 0]);
+        assert_eq!(MusigCommitmentMsg::read(&encoded[..]).unwrap(), msg);
+
+        let msg = MusigNonceMsg {
+            signer: 1,
+            nonce: nonce.clone(),
+        };
+        let mut encoded = vec![];
+        msg.write(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), 36);
+        let decoded = MusigNonceMsg::<Bls12>::read(&encoded[..], params).unwrap();
+        assert_eq!(decoded.signer, 1);
+        assert!(decoded.nonce.0 == nonce.0);
+
+        let msg = MusigPartialSigMsg::<Bls12> {
+            signer: 0,
+            partial: PartialSignature(rng.gen()),
+        };
+        let mut encoded = vec![];
+        msg.write(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), 36);
+        let decoded = MusigPartialSigMsg::<Bls12>::read(&encoded[..]).unwrap();
+        assert_eq!(decoded.signer, 0);
+        assert!(decoded.partial.0 == msg.partial.0);
+
+        // Truncated messages and out-of-range scalars are rejected
+        assert!(MusigNonceMsg::<Bls12>::read(&encoded[..35], params).is_err());
+        encoded[35] = 0xff;
+        assert!(MusigPartialSigMsg::<Bls12>::read(&encoded[..]).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use jubjub::*;
 use pairing::*;
+use constants;
 
 #[derive(Copy, Clone)]
 pub enum Personalization {
@@ -29,10 +30,24 @@ pub fn pedersen_hash<E, I>(
     where I: IntoIterator<Item=bool>,
           E: JubjubEngine
 {
-    let mut bits = personalization.get_bits().into_iter().chain(bits.into_iter());
+    hash_segments(
+        personalization.get_bits().into_iter().chain(bits.into_iter()),
+        params.pedersen_hash_exp_table(),
+        params
+    )
+}
 
+/// Hashes `bits` using the given exp table for the segment generators.
+fn hash_segments<E, I>(
+    mut bits: I,
+    exp_table: &[Vec<Vec<edwards::Point<E, PrimeOrder>>>],
+    params: &E::Params
+) -> edwards::Point<E, PrimeOrder>
+    where I: Iterator<Item=bool>,
+          E: JubjubEngine
+{
     let mut result = edwards::Point::zero();
-    let mut generators = params.pedersen_hash_exp_table().iter();
+    let mut generators = exp_table.iter();
 
     loop {
         let mut acc = E::Fs::zero();
@@ -101,3 +116,132 @@ pub fn pedersen_hash<E, I>(
 
     result
 }
+
+/// The personalizations Sapling uses to derive its generators, which must
+/// not be reused for application generators.
+const SAPLING_GENERATOR_PERSONALIZATIONS: [&'static [u8; 8]; 6] = [
+    constants::PEDERSEN_HASH_GENERATORS_PERSONALIZATION,
+    constants::KEY_DIVERSIFICATION_PERSONALIZATION,
+    constants::SPENDING_KEY_GENERATOR_PERSONALIZATION,
+    constants::PROOF_GENERATION_KEY_BASE_GENERATOR_PERSONALIZATION,
+    constants::VALUE_COMMITMENT_GENERATOR_PERSONALIZATION,
+    constants::NULLIFIER_POSITION_IN_TREE_GENERATOR_PERSONALIZATION
+];
+
+/// Generators for Pedersen hashes and commitments over bit strings, for
+/// applications other than Sapling.
+///
+/// The input length must be a multiple of three bits. The hash pads a short
+/// final chunk with zeros, so if shorter inputs were allowed, `[1]`,
+/// `[1, 0]` and `[1, 0, 0]` would all have the same hash. Whole chunks
+/// never encode to zero, so inputs of different lengths that are multiples
+/// of three do not collide this way.
+///
+/// The generators are derived from a BLAKE2s personalization chosen by the
+/// application, in the same way as Sapling's are derived from its own, so
+/// hashes under different personalizations are independent of each other and
+/// of Sapling's. Unlike `pedersen_hash`, the input is not prefixed with a
+/// `Personalization`.
+pub struct PedersenGenerators<E: JubjubEngine> {
+    max_bits: usize,
+    exp_table: Vec<Vec<Vec<edwards::Point<E, PrimeOrder>>>>,
+    circuit_generators: Vec<Vec<Vec<(E::Fr, E::Fr)>>>,
+    randomness: edwards::Point<E, PrimeOrder>,
+    randomness_circuit_generators: Vec<Vec<(E::Fr, E::Fr)>>
+}
+
+impl<E: JubjubEngine> PedersenGenerators<E> {
+    /// Derives generators for inputs of up to `max_bits` bits.
+    ///
+    /// Panics if `personalization` is one that Sapling uses for its own
+    /// generators.
+    pub fn new(
+        personalization: &[u8; 8],
+        max_bits: usize,
+        params: &E::Params
+    ) -> Self
+    {
+        assert!(
+            !SAPLING_GENERATOR_PERSONALIZATIONS.contains(&personalization),
+            "personalization is reserved for Sapling"
+        );
+
+        let bits_per_segment = 3 * params.pedersen_hash_chunks_per_generator();
+        let num_segments = (max_bits + bits_per_segment - 1) / bits_per_segment;
+
+        let generators: Vec<_> = (0..num_segments).map(|m| {
+            use byteorder::{WriteBytesExt, LittleEndian};
+
+            let mut segment_number = [0u8; 4];
+            (&mut segment_number[0..4]).write_u32::<LittleEndian>(m as u32).unwrap();
+
+            find_group_hash(&segment_number, personalization, params)
+        }).collect();
+
+        let randomness = find_group_hash(b"r", personalization, params);
+
+        PedersenGenerators {
+            max_bits: num_segments * bits_per_segment,
+            exp_table: pedersen_hash_exp_table(&generators, params),
+            circuit_generators: pedersen_circuit_generators(&generators, params),
+            randomness_circuit_generators: fixed_base_circuit_generators(&randomness, params),
+            randomness: randomness
+        }
+    }
+
+    /// Returns the largest number of bits that can be hashed. This is
+    /// `max_bits` as given to `new`, rounded up to a whole segment.
+    pub fn max_bits(&self) -> usize {
+        self.max_bits
+    }
+
+    /// Returns the generator that commitments are randomized over.
+    pub fn randomness_generator(&self) -> &edwards::Point<E, PrimeOrder> {
+        &self.randomness
+    }
+
+    /// Computes the Pedersen hash of `bits`.
+    ///
+    /// Panics if `bits` is empty, if there are more than `max_bits()` bits,
+    /// or if the number of bits is not a multiple of three.
+    pub fn hash<I>(
+        &self,
+        bits: I,
+        params: &E::Params
+    ) -> edwards::Point<E, PrimeOrder>
+        where I: IntoIterator<Item=bool>
+    {
+        let bits: Vec<bool> = bits.into_iter().collect();
+        assert!(!bits.is_empty(), "no bits to hash");
+        assert!(bits.len() <= self.max_bits, "too many bits");
+        assert!(bits.len() % 3 == 0, "number of bits is not a multiple of three");
+
+        hash_segments(bits.into_iter(), &self.exp_table, params)
+    }
+
+    /// Computes the Pedersen commitment to `bits` with the given randomness,
+    /// which is the hash of `bits` plus `randomness` times the randomness
+    /// generator.
+    ///
+    /// Panics if `bits` is empty, if there are more than `max_bits()` bits,
+    /// or if the number of bits is not a multiple of three.
+    pub fn commit<I>(
+        &self,
+        bits: I,
+        randomness: E::Fs,
+        params: &E::Params
+    ) -> edwards::Point<E, PrimeOrder>
+        where I: IntoIterator<Item=bool>
+    {
+        self.hash(bits, params)
+            .add(&self.randomness.mul(randomness, params), params)
+    }
+
+    pub(crate) fn circuit_generators(&self) -> &[Vec<Vec<(E::Fr, E::Fr)>>] {
+        &self.circuit_generators
+    }
+
+    pub(crate) fn randomness_circuit_generators(&self) -> &[Vec<(E::Fr, E::Fr)>] {
+        &self.randomness_circuit_generators
+    }
+}
@@ -26,7 +26,41 @@ use jubjub::{
     FixedGenerators
 };
 
-use blake2_rfc::blake2s::Blake2s;
+use musig::{self, MusigError};
+
+use std::io::{self, Read, Write};
+
+pub mod prf;
+
+/// The total supply of 21 million ZEC, in zatoshis. This is
+/// `zcash_primitives::amount::MAX_MONEY`, which this crate cannot depend on.
+const MAX_MONEY: u64 = 21_000_000 * 100_000_000;
+
+/// The value of a Sapling note, in zatoshis. It is never more than the total
+/// supply of ZEC.
+///
+/// The note commitment is defined for any 64-bit value, but a note above the
+/// total supply can never be valid, so wallets should only create notes (and
+/// accept decrypted ones) through this type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SaplingNoteValue(u64);
+
+impl SaplingNoteValue {
+    /// Returns `None` if the value is above the total supply of ZEC.
+    pub fn from_u64(value: u64) -> Option<Self> {
+        if value <= MAX_MONEY {
+            Some(SaplingNoteValue(value))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<SaplingNoteValue> for u64 {
+    fn from(value: SaplingNoteValue) -> u64 {
+        value.0
+    }
+}
 
 #[derive(Clone)]
 pub struct ValueCommitment<E: JubjubEngine> {
@@ -85,17 +119,13 @@ impl<E: JubjubEngine> ViewingKey<E> {
     }
 
     pub fn ivk(&self) -> E::Fs {
-        let mut preimage = [0; 64];
-
-        self.ak.write(&mut preimage[0..32]).unwrap();
-        self.nk.write(&mut preimage[32..64]).unwrap();
+        let mut ak = [0; 32];
+        let mut nk = [0; 32];
 
-        let mut h = Blake2s::with_params(32, &[], &[], constants::CRH_IVK_PERSONALIZATION);
-        h.update(&preimage);
-        let mut h = h.finalize().as_ref().to_vec();
+        self.ak.write(&mut ak[..]).unwrap();
+        self.nk.write(&mut nk[..]).unwrap();
 
-        // Drop the most significant five bits, so it can be interpreted as a scalar.
-        h[31] &= 0b0000_0111;
+        let h = prf::crh_ivk(&ak, &nk);
 
         let mut e = <E::Fs as PrimeField>::Repr::default();
         e.read_le(&h[..]).unwrap();
@@ -103,41 +133,101 @@ impl<E: JubjubEngine> ViewingKey<E> {
         E::Fs::from_repr(e).expect("should be a valid scalar")
     }
 
+    pub fn incoming_viewing_key(&self) -> IncomingViewingKey<E> {
+        IncomingViewingKey(self.ivk())
+    }
+
     pub fn into_payment_address(
         &self,
         diversifier: Diversifier,
         params: &E::Params
     ) -> Option<PaymentAddress<E>>
     {
-        diversifier.g_d(params).map(|g_d| {
-            let pk_d = g_d.mul(self.ivk(), params);
-
-            PaymentAddress {
-                pk_d: pk_d,
-                diversifier: diversifier
-            }
-        })
+        self.incoming_viewing_key().to_payment_address(diversifier, params)
     }
 
+    /// Returns the viewing key whose `ak` is the MuSig joint key of this
+    /// key's `ak` and a co-signer's `ak_2`, keeping this key's `nk`.
+    ///
+    /// The keys are delinearized as in `musig::aggregate_keys`, so the
+    /// co-signer cannot choose `ak_2` to cancel out `ak`.
     pub fn make_multisig_with(
         &self,
         ak_2: edwards::Point<E, PrimeOrder>,
         params: &E::Params
-    ) -> ViewingKey<E>
-    {   //TODO: randomize the resultant key with hash to avoid known attacks
-        ViewingKey{ak: self.ak.add(&ak_2, params),nk: self.nk.clone()}
+    ) -> Result<ViewingKey<E>, MusigError>
+    {
+        let ak = musig::aggregate_keys(&[self.ak.clone(), ak_2], params)?;
+
+        Ok(ViewingKey {
+            ak: ak,
+            nk: self.nk.clone()
+        })
     }
 
+    /// Returns the payment address of the multisig key for the given
+    /// diversifier, or `None` if the diversifier is invalid.
     pub fn make_multisig_address_with(
         &self,
         ak_2: edwards::Point<E, PrimeOrder>,
+        diversifier: Diversifier,
         params: &E::Params
-    ) -> PaymentAddress<E>
-    {   //TODO: randomize the resultant key with hash to avoid known attacks
-        ViewingKey{ak: self.ak.add(&ak_2, params),nk: self.nk.clone()}.into_payment_address(Diversifier([0u8;11]),params).unwrap()
+    ) -> Result<Option<PaymentAddress<E>>, MusigError>
+    {
+        let viewing_key = self.make_multisig_with(ak_2, params)?;
+
+        Ok(viewing_key.into_payment_address(diversifier, params))
     }
   }
 
+/// An incoming viewing key. It can derive payment addresses and detect the
+/// notes sent to them, but cannot detect spends.
+#[derive(Clone)]
+pub struct IncomingViewingKey<E: JubjubEngine>(pub E::Fs);
+
+impl<E: JubjubEngine> IncomingViewingKey<E> {
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let mut repr = <E::Fs as PrimeField>::Repr::default();
+        repr.read_le(reader)?;
+
+        // ivk is the output of CRH^ivk truncated to 251 bits
+        if repr.num_bits() > 251 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "ivk is not in the range of CRH^ivk",
+            ));
+        }
+
+        match E::Fs::from_repr(repr) {
+            Ok(ivk) => Ok(IncomingViewingKey(ivk)),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "ivk is not in field",
+            )),
+        }
+    }
+
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.into_repr().write_le(writer)
+    }
+
+    pub fn to_payment_address(
+        &self,
+        diversifier: Diversifier,
+        params: &E::Params
+    ) -> Option<PaymentAddress<E>>
+    {
+        diversifier.g_d(params).map(|g_d| {
+            let pk_d = g_d.mul(self.0, params);
+
+            PaymentAddress {
+                pk_d: pk_d,
+                diversifier: diversifier
+            }
+        })
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Diversifier(pub [u8; 11]);
 
@@ -168,14 +258,14 @@ impl<E: JubjubEngine> PaymentAddress<E> {
 
     pub fn create_note(
         &self,
-        value: u64,
+        value: SaplingNoteValue,
         randomness: E::Fs,
         params: &E::Params
     ) -> Option<Note<E>>
     {
         self.g_d(params).map(|g_d| {
             Note {
-                value: value,
+                value: value.into(),
                 r: randomness,
                 g_d: g_d,
                 pk_d: self.pk_d.clone()
@@ -257,13 +347,66 @@ impl<E: JubjubEngine> Note<E> {
             );
 
         // Compute nf = BLAKE2s(nk | rho)
-        let mut nf_preimage = [0u8; 64];
-        viewing_key.nk.write(&mut nf_preimage[0..32]).unwrap();
-        rho.write(&mut nf_preimage[32..64]).unwrap();
-        let mut h = Blake2s::with_params(32, &[], &[], constants::PRF_NF_PERSONALIZATION);
-        h.update(&nf_preimage);
-        
-        h.finalize().as_ref().to_vec()
+        let mut nk = [0u8; 32];
+        let mut rho_bytes = [0u8; 32];
+        viewing_key.nk.write(&mut nk[..]).unwrap();
+        rho.write(&mut rho_bytes[..]).unwrap();
+
+        prf::prf_nf(&nk, &rho_bytes).to_vec()
+    }
+
+    /// Computes the nullifiers of many notes, each given with its position,
+    /// under the same viewing key.
+    ///
+    /// The results are the same as calling `nf` on each note, but the
+    /// multiples of the position generator are looked up in a window table
+    /// that is built once for the whole batch, rather than computed by a
+    /// full scalar multiplication per note.
+    pub fn nf_batch(
+        viewing_key: &ViewingKey<E>,
+        notes: &[(Note<E>, u64)],
+        params: &E::Params
+    ) -> Vec<Vec<u8>>
+    {
+        if notes.is_empty() {
+            return vec![];
+        }
+
+        // Build tables of [0..16] times G * 16^i for each 4-bit window of
+        // the position
+        let mut tables = Vec::with_capacity(16);
+        let mut base = params.generator(FixedGenerators::NullifierPosition).clone();
+        for _ in 0..16 {
+            let mut table = Vec::with_capacity(16);
+            let mut multiple = edwards::Point::zero();
+            for _ in 0..16 {
+                table.push(multiple.clone());
+                multiple = multiple.add(&base, params);
+            }
+            tables.push(table);
+
+            // base = base * 16
+            base = multiple;
+        }
+
+        let mut nk = [0u8; 32];
+        viewing_key.nk.write(&mut nk[..]).unwrap();
+
+        notes.iter().map(|&(ref note, position)| {
+            // Compute rho = cm + position.G
+            let mut rho = note.cm_full_point(params);
+            for (i, table) in tables.iter().enumerate() {
+                let window = ((position >> (4 * i)) & 0xf) as usize;
+                if window != 0 {
+                    rho = rho.add(&table[window], params);
+                }
+            }
+
+            let mut rho_bytes = [0u8; 32];
+            rho.write(&mut rho_bytes[..]).unwrap();
+
+            prf::prf_nf(&nk, &rho_bytes).to_vec()
+        }).collect()
     }
 
     /// Computes the note commitment
@@ -274,3 +417,87 @@ impl<E: JubjubEngine> Note<E> {
         self.cm_full_point(params).into_xy().0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pairing::bls12_381::Bls12;
+    use rand::{SeedableRng, XorShiftRng};
+
+    use jubjub::{edwards, JubjubBls12};
+    use musig::{aggregate_keys, MusigError};
+    use testing::{random_note, random_payment_address, random_proof_generation_key};
+
+    use super::{Diversifier, Note, SaplingNoteValue, MAX_MONEY};
+
+    #[test]
+    fn nf_batch_matches_nf() {
+        let params = &JubjubBls12::new();
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let pgk = random_proof_generation_key::<Bls12, _>(rng, params);
+        let viewing_key = pgk.into_viewing_key(params);
+        let address = random_payment_address(&pgk, rng, params);
+
+        let notes: Vec<_> = [0, 1, 15, 16, 0x1234_5678_9abc_def0, u64::max_value()]
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| {
+                let value = SaplingNoteValue::from_u64(i as u64 * 1000).unwrap();
+                (random_note(&address, value, rng, params), position)
+            })
+            .collect();
+
+        let batch = Note::nf_batch(&viewing_key, &notes, params);
+        assert_eq!(batch.len(), notes.len());
+        for (&(ref note, position), nf) in notes.iter().zip(batch.iter()) {
+            assert_eq!(&note.nf(&viewing_key, position, params), nf);
+        }
+
+        assert!(Note::nf_batch(&viewing_key, &[], params).is_empty());
+    }
+
+    #[test]
+    fn note_value_range() {
+        assert_eq!(SaplingNoteValue::from_u64(0).map(u64::from), Some(0));
+        assert_eq!(
+            SaplingNoteValue::from_u64(MAX_MONEY).map(u64::from),
+            Some(MAX_MONEY)
+        );
+        assert!(SaplingNoteValue::from_u64(MAX_MONEY + 1).is_none());
+        assert!(SaplingNoteValue::from_u64(u64::max_value()).is_none());
+    }
+
+    #[test]
+    fn multisig_viewing_key() {
+        let params = &JubjubBls12::new();
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+        let viewing_key = random_proof_generation_key::<Bls12, _>(rng, params).into_viewing_key(params);
+        let ak_2 = random_proof_generation_key::<Bls12, _>(rng, params).ak;
+
+        let multisig = viewing_key.make_multisig_with(ak_2.clone(), params).unwrap();
+        let expected = aggregate_keys(&[viewing_key.ak.clone(), ak_2.clone()], params).unwrap();
+        assert!(multisig.ak == expected);
+        assert!(multisig.ak != viewing_key.ak.add(&ak_2, params));
+        assert!(multisig.nk == viewing_key.nk);
+
+        let diversifier = (0..).map(|i| Diversifier([i; 11]))
+            .find(|d| d.g_d::<Bls12>(params).is_some())
+            .unwrap();
+        let address = viewing_key
+            .make_multisig_address_with(ak_2, diversifier, params)
+            .unwrap()
+            .unwrap();
+        assert!(address.pk_d == multisig.into_payment_address(diversifier, params).unwrap().pk_d);
+
+        // Invalid co-signer keys are rejected
+        assert_eq!(
+            viewing_key.make_multisig_with(viewing_key.ak.clone(), params).err(),
+            Some(MusigError::DuplicateKey)
+        );
+        assert_eq!(
+            viewing_key.make_multisig_with(edwards::Point::zero(), params).err(),
+            Some(MusigError::InvalidKey)
+        );
+    }
+}
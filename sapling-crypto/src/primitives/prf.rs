@@ -0,0 +1,45 @@
+//! The BLAKE2s-based functions used in Sapling key and nullifier derivation,
+//! computed over the byte encodings of their inputs.
+//!
+//! These are the same constructions `ViewingKey::ivk` and `Note::nf` use, for
+//! callers that already have the encoded points.
+
+use blake2_rfc::blake2s::Blake2s;
+
+use constants;
+
+/// Computes CRH^ivk(ak, nk), the incoming viewing key for the encoded
+/// spend validating key `ak` and nullifier deriving key `nk`.
+///
+/// This is BLAKE2s-256 of `ak | nk` with personalization `Zcashivk`, with
+/// the five most significant bits cleared. The result is therefore always
+/// the little-endian encoding of a valid Jubjub scalar.
+pub fn crh_ivk(ak: &[u8; 32], nk: &[u8; 32]) -> [u8; 32] {
+    let mut h = Blake2s::with_params(32, &[], &[], constants::CRH_IVK_PERSONALIZATION);
+    h.update(ak);
+    h.update(nk);
+
+    let mut result = [0u8; 32];
+    result.copy_from_slice(h.finalize().as_ref());
+
+    // Drop the most significant five bits, so it can be interpreted as a scalar.
+    result[31] &= 0b0000_0111;
+
+    result
+}
+
+/// Computes PRF^nf_nk(rho), the nullifier of a note with the encoded
+/// nullifier deriving key `nk` and the encoded point `rho`, which is the
+/// note commitment plus the position of the note in the tree times the
+/// `NullifierPosition` generator.
+///
+/// This is BLAKE2s-256 of `nk | rho` with personalization `Zcash_nf`.
+pub fn prf_nf(nk: &[u8; 32], rho: &[u8; 32]) -> [u8; 32] {
+    let mut h = Blake2s::with_params(32, &[], &[], constants::PRF_NF_PERSONALIZATION);
+    h.update(nk);
+    h.update(rho);
+
+    let mut result = [0u8; 32];
+    result.copy_from_slice(h.finalize().as_ref());
+    result
+}
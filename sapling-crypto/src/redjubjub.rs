@@ -5,10 +5,10 @@ use pairing::{Field, PrimeField, PrimeFieldRepr};
 use rand::{Rng, Rand};
 use std::io::{self, Read, Write};
 
-use jubjub::{FixedGenerators, JubjubEngine, JubjubParams, Unknown, edwards::Point};
+use jubjub::{FixedGenerators, JubjubEngine, JubjubParams, Unknown, Zeroize, edwards::Point};
 use util::{hash_to_scalar};
 
-fn read_scalar<E: JubjubEngine, R: Read>(reader: R) -> io::Result<E::Fs> {
+pub(crate) fn read_scalar<E: JubjubEngine, R: Read>(reader: R) -> io::Result<E::Fs> {
     let mut s_repr = <E::Fs as PrimeField>::Repr::default();
     s_repr.read_le(reader)?;
 
@@ -21,18 +21,41 @@ fn read_scalar<E: JubjubEngine, R: Read>(reader: R) -> io::Result<E::Fs> {
     }
 }
 
-fn write_scalar<E: JubjubEngine, W: Write>(s: &E::Fs, writer: W) -> io::Result<()> {
+pub(crate) fn write_scalar<E: JubjubEngine, W: Write>(s: &E::Fs, writer: W) -> io::Result<()> {
     s.into_repr().write_le(writer)
 }
 
-fn h_star<E: JubjubEngine>(a: &[u8], b: &[u8]) -> E::Fs {
+pub(crate) fn h_star<E: JubjubEngine>(a: &[u8], b: &[u8]) -> E::Fs {
     hash_to_scalar::<E>(b"Zcash_RedJubjubH", a, b)
 }
 
+/// Derives a signing nonce `r = H*(T || sk || M)` from fresh randomness `T`,
+/// the signing key and the message.
+///
+/// The randomness alone is enough for a secure nonce. The key and message
+/// are hashed in as well so that a broken RNG that repeats `T` can only
+/// repeat a nonce for the same key and message, where it does no harm,
+/// rather than revealing the key.
+pub(crate) fn synthetic_nonce<E: JubjubEngine, R: Rng>(sk: &E::Fs, msg: &[u8], rng: &mut R) -> E::Fs {
+    // T = (l_H + 128) bits of randomness
+    // For H*, l_H = 512 bits
+    let mut t = [0u8; 80 + 32];
+    rng.fill_bytes(&mut t[..80]);
+    write_scalar::<E, &mut [u8]>(sk, &mut t[80..])
+        .expect("Jubjub scalars should serialize to 32 bytes");
+
+    let r = h_star::<E>(&t[..], msg);
+
+    // t holds a copy of sk
+    t[..].zeroize();
+
+    r
+}
+
 #[derive(Copy, Clone)]
 pub struct Signature {
-    rbar: [u8; 32],
-    sbar: [u8; 32],
+    pub(crate) rbar: [u8; 32],
+    pub(crate) sbar: [u8; 32],
 }
 
 pub struct PrivateKey<E: JubjubEngine>(pub E::Fs);
@@ -77,13 +100,7 @@ impl<E: JubjubEngine> PrivateKey<E> {
         p_g: FixedGenerators,
         params: &E::Params,
     ) -> Signature {
-        // T = (l_H + 128) bits of randomness
-        // For H*, l_H = 512 bits
-        let mut t = [0u8; 80];
-        rng.fill_bytes(&mut t[..]);
-
-        // r = H*(T || M)
-        let r = h_star::<E>(&t[..], msg);
+        let r = synthetic_nonce::<E, R>(&self.0, msg, rng);
 
         // R = r . P_G
         let r_g = params.generator(p_g).mul(r, params);
@@ -205,7 +222,7 @@ pub fn batch_verify<'a, E: JubjubEngine, R: Rng>(
 #[cfg(test)]
 mod tests {
     use pairing::bls12_381::Bls12;
-    use rand::thread_rng;
+    use rand::{thread_rng, SeedableRng, XorShiftRng};
 
     use jubjub::{JubjubBls12, fs::Fs, edwards};
 
@@ -340,4 +357,24 @@ mod tests {
             assert!(!rvk.verify(msg2, &sig1, p_g, params));
         }
     }
+
+    #[test]
+    fn repeated_randomness() {
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+        let params = &JubjubBls12::new();
+        let seed = [0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654];
+
+        let sk1 = PrivateKey::<Bls12>(XorShiftRng::from_seed([1, 2, 3, 4]).gen());
+        let sk2 = PrivateKey::<Bls12>(XorShiftRng::from_seed([5, 6, 7, 8]).gen());
+
+        // An RNG that always returns the same bytes only repeats the nonce
+        // for the same key and message
+        let rbar = |sk: &PrivateKey<Bls12>, msg: &[u8]| {
+            let rng = &mut XorShiftRng::from_seed(seed);
+            sk.sign(msg, rng, p_g, params).rbar
+        };
+        assert_eq!(rbar(&sk1, b"Foo bar"), rbar(&sk1, b"Foo bar"));
+        assert!(rbar(&sk1, b"Foo bar") != rbar(&sk1, b"Spam eggs"));
+        assert!(rbar(&sk1, b"Foo bar") != rbar(&sk2, b"Foo bar"));
+    }
 }
@@ -0,0 +1,145 @@
+//! Generators of valid keys, addresses, notes and commitment tree witnesses,
+//! for tests of code built on this crate.
+//!
+//! All generators draw from the given RNG, so seeding it (for example with
+//! `XorShiftRng::from_seed`) makes the results deterministic.
+
+use rand::Rng;
+
+use jubjub::{FixedGenerators, JubjubEngine, JubjubParams};
+use merkle_tree::CommitmentTreeWitness;
+use primitives::{Diversifier, Note, PaymentAddress, ProofGenerationKey, SaplingNoteValue};
+
+/// Generates a random proof generation key.
+pub fn random_proof_generation_key<E: JubjubEngine, R: Rng>(
+    rng: &mut R,
+    params: &E::Params
+) -> ProofGenerationKey<E>
+{
+    let ask: E::Fs = rng.gen();
+
+    ProofGenerationKey {
+        ak: params.generator(FixedGenerators::SpendingKeyGenerator).mul(ask, params),
+        nsk: rng.gen()
+    }
+}
+
+/// Generates a random payment address for the given proof generation key,
+/// retrying until the diversifier is valid.
+pub fn random_payment_address<E: JubjubEngine, R: Rng>(
+    proof_generation_key: &ProofGenerationKey<E>,
+    rng: &mut R,
+    params: &E::Params
+) -> PaymentAddress<E>
+{
+    let viewing_key = proof_generation_key.into_viewing_key(params);
+
+    loop {
+        let diversifier = Diversifier(rng.gen());
+
+        if let Some(address) = viewing_key.into_payment_address(diversifier, params) {
+            return address;
+        }
+    }
+}
+
+/// Generates a note of the given value, with random commitment randomness,
+/// sent to the given address.
+pub fn random_note<E: JubjubEngine, R: Rng>(
+    address: &PaymentAddress<E>,
+    value: SaplingNoteValue,
+    rng: &mut R,
+    params: &E::Params
+) -> Note<E>
+{
+    address
+        .create_note(value, rng.gen(), params)
+        .expect("address has a valid diversifier")
+}
+
+/// Generates a witness for a commitment at `position` in a tree of the given
+/// depth, with random siblings.
+///
+/// The siblings do not come from a real tree of notes, but the path is
+/// consistent with its root, which is all the Spend circuit checks. Use
+/// `CommitmentTreeWitness::root` to get the anchor.
+pub fn random_witness<E: JubjubEngine, R: Rng>(
+    position: u64,
+    depth: usize,
+    rng: &mut R
+) -> CommitmentTreeWitness<E>
+{
+    assert!(depth <= 64 && (depth == 64 || position >> depth == 0));
+
+    CommitmentTreeWitness {
+        auth_path: (0..depth).map(|i| (rng.gen(), (position >> i) & 1 == 1)).collect(),
+        position: position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bellman::ConstraintSystem;
+    use bellman::Circuit;
+    use pairing::bls12_381::Bls12;
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    use circuit::sapling::Spend;
+    use circuit::test::TestConstraintSystem;
+    use jubjub::JubjubBls12;
+    use primitives::ValueCommitment;
+
+    use super::*;
+
+    #[test]
+    fn generators_are_deterministic() {
+        let params = &JubjubBls12::new();
+        let seed = [0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654];
+
+        let cms: Vec<_> = (0..2).map(|_| {
+            let rng = &mut XorShiftRng::from_seed(seed);
+            let pgk = random_proof_generation_key::<Bls12, _>(rng, params);
+            let address = random_payment_address(&pgk, rng, params);
+            let value = SaplingNoteValue::from_u64(1000).unwrap();
+            random_note(&address, value, rng, params).cm(params)
+        }).collect();
+
+        assert_eq!(cms[0], cms[1]);
+    }
+
+    #[test]
+    fn witness_satisfies_spend_circuit() {
+        let params = &JubjubBls12::new();
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let tree_depth = 32;
+
+        let pgk = random_proof_generation_key::<Bls12, _>(rng, params);
+        let address = random_payment_address(&pgk, rng, params);
+        let value = SaplingNoteValue::from_u64(1000).unwrap();
+        let note = random_note(&address, value, rng, params);
+        let witness = random_witness::<Bls12, _>(0x1234_5678, tree_depth, rng);
+
+        assert_eq!(witness.position, 0x1234_5678);
+        assert_eq!(witness.auth_path.len(), tree_depth);
+
+        let mut cs = TestConstraintSystem::<Bls12>::new();
+
+        let instance = Spend {
+            params: params,
+            value_commitment: Some(ValueCommitment {
+                value: note.value,
+                randomness: rng.gen()
+            }),
+            proof_generation_key: Some(pgk),
+            payment_address: Some(address),
+            commitment_randomness: Some(note.r),
+            ar: Some(rng.gen()),
+            auth_path: witness.circuit_auth_path(),
+            anchor: Some(witness.root(&note.cm(params), params))
+        };
+
+        instance.synthesize(&mut cs.namespace(|| "spend")).unwrap();
+
+        assert!(cs.is_satisfied());
+    }
+}
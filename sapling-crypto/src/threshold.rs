@@ -0,0 +1,513 @@
+//! Threshold (t-of-n) signing of RedJubjub spend authorization signatures.
+//!
+//! The spend authorizing key `ask` is split into `n` Shamir shares, any `t`
+//! of which can sign. The shares are points on a random polynomial `f` of
+//! degree `t - 1` over `Fs` with `f(0) = ask`, and co-signer `i` holds
+//! `f(i)`. The spend validating key is the usual `ak = [ask] P_G`, so notes
+//! are received and proven for exactly as for a single-signer key.
+//!
+//! To sign, a set of at least `t` co-signers run the MuSig session from
+//! the `musig` module: the same nonce round, partial signatures and
+//! aggregation. The only difference is that co-signer `i` signs with
+//! `lambda_i . f(i)`, where `lambda_i` is its Lagrange coefficient for the
+//! signing set, so that the weighted shares sum to `ask`.
+//!
+//! Shares are generated by a dealer who knows `ask`, such as the wallet
+//! that created the key. The dealer also publishes Feldman commitments to
+//! the polynomial, so that each co-signer can check that its share is
+//! consistent with `ak` and with everyone else's.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use pairing::{Field, PrimeField};
+use rand::Rng;
+use std::io::{self, Read, Write};
+use std::iter;
+
+use jubjub::{
+    edwards::Point, FixedGenerators, JubjubEngine, JubjubParams, PrimeOrder, Unknown, Zeroize,
+};
+use musig::{
+    aggregate_randomizers, MusigError, RandomizerCommitment, RandomizerContribution, SigningSession,
+};
+use redjubjub::{read_scalar, write_scalar, PublicKey};
+
+/// The errors that can occur while splitting a key or starting a threshold
+/// signing session.
+#[derive(Debug, PartialEq)]
+pub enum ThresholdError {
+    /// The threshold was zero or larger than the number of shares.
+    InvalidThreshold,
+    /// Fewer co-signers than the threshold were in the signing set.
+    NotEnoughSigners,
+    /// The signing set contained index zero or a repeated index.
+    InvalidSigners,
+    /// The co-signer's own index was not in the signing set.
+    MissingOwnIndex,
+}
+
+/// A co-signer's Shamir share `f(index)` of the spend authorizing key.
+pub struct KeyShare<E: JubjubEngine> {
+    index: u32,
+    threshold: usize,
+    share: E::Fs,
+    ak: Point<E, PrimeOrder>,
+}
+
+impl<E: JubjubEngine> Drop for KeyShare<E> {
+    fn drop(&mut self) {
+        self.share.zeroize();
+    }
+}
+
+fn index_to_scalar<E: JubjubEngine>(index: u32) -> E::Fs {
+    E::Fs::from_repr((index as u64).into()).expect("u32 should be in field")
+}
+
+/// Splits `ask` into `num_shares` shares with indices `1..=num_shares`, any
+/// `threshold` of which can sign.
+///
+/// Also returns the Feldman commitments `[a_k] P_G` to the coefficients of
+/// `f`, starting with `[ask] P_G = ak`. These are public, and each co-signer
+/// should check its share against them with `KeyShare::verify`.
+pub fn split_key<E: JubjubEngine, R: Rng>(
+    ask: E::Fs,
+    threshold: usize,
+    num_shares: u32,
+    rng: &mut R,
+    params: &E::Params,
+) -> Result<(Vec<KeyShare<E>>, Vec<Point<E, PrimeOrder>>), ThresholdError> {
+    if threshold == 0 || threshold > num_shares as usize {
+        return Err(ThresholdError::InvalidThreshold);
+    }
+
+    // f(x) = ask + coeffs[0] . x + ... + coeffs[t - 2] . x^(t - 1)
+    let mut coeffs: Vec<E::Fs> = (1..threshold).map(|_| rng.gen()).collect();
+    let p_g = params.generator(FixedGenerators::SpendingKeyGenerator);
+    let ak = p_g.mul(ask, params);
+    let commitments = iter::once(ak.clone())
+        .chain(coeffs.iter().map(|coeff| p_g.mul(*coeff, params)))
+        .collect();
+
+    let shares = (0..num_shares)
+        .map(|i| {
+            let index = i + 1;
+            let x = index_to_scalar::<E>(index);
+
+            // Horner's rule
+            let mut share = E::Fs::zero();
+            for coeff in coeffs.iter().rev() {
+                share.add_assign(coeff);
+                share.mul_assign(&x);
+            }
+            share.add_assign(&ask);
+
+            KeyShare {
+                index,
+                threshold,
+                share,
+                ak: ak.clone(),
+            }
+        })
+        .collect();
+
+    // The coefficients determine ask as much as the shares do
+    for coeff in coeffs.iter_mut() {
+        coeff.zeroize();
+    }
+
+    Ok((shares, commitments))
+}
+
+/// Returns the Lagrange coefficient at zero of `index` within `signers`,
+/// which must contain distinct non-zero indices including `index`.
+fn lagrange_coefficient<E: JubjubEngine>(index: u32, signers: &[u32]) -> E::Fs {
+    let x_i = index_to_scalar::<E>(index);
+
+    let mut num = E::Fs::one();
+    let mut den = E::Fs::one();
+    for &j in signers.iter().filter(|j| **j != index) {
+        let x_j = index_to_scalar::<E>(j);
+        num.mul_assign(&x_j);
+
+        let mut diff = x_j;
+        diff.sub_assign(&x_i);
+        den.mul_assign(&diff);
+    }
+
+    let mut coeff = den.inverse().expect("indices are distinct");
+    coeff.mul_assign(&num);
+    coeff
+}
+
+impl<E: JubjubEngine> KeyShare<E> {
+    /// Reads a share written by `write`. This does not check the share
+    /// against the dealer's commitments.
+    pub fn read<R: Read>(mut reader: R, params: &E::Params) -> io::Result<Self> {
+        let index = reader.read_u32::<LittleEndian>()?;
+        if index == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "share index is zero",
+            ));
+        }
+        let threshold = reader.read_u32::<LittleEndian>()?;
+        if threshold == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "threshold is zero",
+            ));
+        }
+
+        let mut share = read_scalar::<E, _>(&mut reader)?;
+        let ak = match Point::<E, Unknown>::read(&mut reader, params) {
+            Ok(ak) => ak.as_prime_order(params),
+            Err(e) => {
+                share.zeroize();
+                return Err(e);
+            }
+        };
+        let ak = match ak {
+            Some(ak) => ak,
+            None => {
+                share.zeroize();
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "ak not of prime order",
+                ));
+            }
+        };
+
+        Ok(KeyShare {
+            index,
+            threshold: threshold as usize,
+            share,
+            ak,
+        })
+    }
+
+    /// Writes the share as its index and threshold, each a little-endian
+    /// `u32`, followed by `f(index)` and `ak`. This includes the secret
+    /// share itself.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<LittleEndian>(self.index)?;
+        writer.write_u32::<LittleEndian>(self.threshold as u32)?;
+        write_scalar::<E, _>(&self.share, &mut writer)?;
+        self.ak.write(writer)
+    }
+
+    /// Checks this share against the dealer's commitments from `split_key`.
+    /// They must commit to a polynomial of degree `threshold - 1` for this
+    /// share's `ak`, whose value at `index` is `[f(index)] P_G`.
+    pub fn verify(&self, commitments: &[Point<E, PrimeOrder>], params: &E::Params) -> bool {
+        if commitments.len() != self.threshold || commitments.first() != Some(&self.ak) {
+            return false;
+        }
+
+        // Horner's rule, over the commitments
+        let x = index_to_scalar::<E>(self.index);
+        let mut expected = Point::zero();
+        for commitment in commitments.iter().rev() {
+            expected = expected.mul(x, params).add(commitment, params);
+        }
+
+        params
+            .generator(FixedGenerators::SpendingKeyGenerator)
+            .mul(self.share, params)
+            == expected
+    }
+
+    /// Returns the index of this share. This is its co-signer's index in the
+    /// messages of a signing session.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the number of co-signers needed to sign.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the spend validating key `ak` that the shares sign for.
+    pub fn ak(&self) -> &Point<E, PrimeOrder> {
+        &self.ak
+    }
+
+    /// Returns the joint randomizer `ar` and the randomized key `rk` for the
+    /// signing set's revealed randomizer contributions. See
+    /// `musig::aggregate_randomizers`.
+    pub fn randomize(
+        &self,
+        commitments: &[RandomizerCommitment],
+        contributions: &[RandomizerContribution],
+        params: &E::Params,
+    ) -> Result<(E::Fs, PublicKey<E>), MusigError> {
+        aggregate_randomizers(&self.ak, commitments, contributions, params)
+    }
+
+    /// Starts a session to sign `sighash` with a fresh nonce, among the
+    /// co-signers with the given indices. Every co-signer in the session
+    /// must use the same signing set.
+    ///
+    /// `session_id` must not have been used before with this share, even by
+    /// an earlier run of the process, as for
+    /// `MusigParticipant::start_session`.
+    pub fn start_session<R: Rng>(
+        &self,
+        session_id: u64,
+        signers: &[u32],
+        sighash: &[u8; 32],
+        rng: &mut R,
+        params: &E::Params,
+    ) -> Result<SigningSession<E>, ThresholdError> {
+        let mut sorted = signers.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        if sorted.len() != signers.len() || sorted.first() == Some(&0) {
+            return Err(ThresholdError::InvalidSigners);
+        }
+        if signers.len() < self.threshold {
+            return Err(ThresholdError::NotEnoughSigners);
+        }
+        if !signers.contains(&self.index) {
+            return Err(ThresholdError::MissingOwnIndex);
+        }
+
+        let mut ask = lagrange_coefficient::<E>(self.index, signers);
+        ask.mul_assign(&self.share);
+
+        // The nonce is bound to the key, the signing set and the sighash
+        let mut context = vec![];
+        self.ak
+            .write(&mut context)
+            .expect("writing to a Vec should not fail");
+        for index in sorted {
+            context
+                .write_u32::<LittleEndian>(index)
+                .expect("writing to a Vec should not fail");
+        }
+        context.extend_from_slice(sighash);
+        let session = SigningSession::new(ask, session_id, &context, rng, params);
+
+        // The session holds its own copy of the weighted share
+        ask.zeroize();
+
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pairing::bls12_381::{Bls12, Fr};
+    use pairing::PrimeFieldRepr;
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    use jubjub::{FixedGenerators, JubjubBls12, JubjubEngine};
+    use musig::{aggregate, aggregate_nonces};
+    use primitives::ProofGenerationKey;
+    use redjubjub::Signature;
+
+    use super::*;
+
+    fn sign(
+        shares: &[&KeyShare<Bls12>],
+        session_id: u64,
+        signers: &[u32],
+        rk: &PublicKey<Bls12>,
+        ar: <Bls12 as JubjubEngine>::Fs,
+        sighash: &[u8; 32],
+        rng: &mut XorShiftRng,
+        params: &JubjubBls12,
+    ) -> Signature {
+        let sessions: Vec<_> = shares
+            .iter()
+            .map(|share| {
+                share
+                    .start_session(session_id, signers, sighash, rng, params)
+                    .unwrap()
+            })
+            .collect();
+        let commitments: Vec<_> = sessions.iter().map(|s| s.nonce_commitment()).collect();
+        let nonces: Vec<_> = sessions.iter().map(|s| s.public_nonce().clone()).collect();
+        let joint_nonce = aggregate_nonces(&commitments, &nonces, params).unwrap();
+
+        let partials: Vec<_> = sessions
+            .into_iter()
+            .map(|s| s.partial_sign(&joint_nonce, rk, sighash))
+            .collect();
+        aggregate(&joint_nonce, rk, ar, sighash, &partials)
+    }
+
+    #[test]
+    fn two_of_three() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+        let p_g = FixedGenerators::SpendingKeyGenerator;
+
+        let ask = rng.gen();
+        let (shares, _) = split_key::<Bls12, _>(ask, 2, 3, rng, params).unwrap();
+
+        // The shares sign for the usual viewing key and rk
+        let viewing_key = ProofGenerationKey {
+            ak: shares[0].ak().clone(),
+            nsk: rng.gen(),
+        }
+        .into_viewing_key(params);
+        let contributions: Vec<_> = (0..2)
+            .map(|_| RandomizerContribution::generate(rng))
+            .collect();
+        let commitments: Vec<_> = contributions.iter().map(|c| c.commit()).collect();
+        let (ar, rk) = shares[0]
+            .randomize(&commitments, &contributions, params)
+            .unwrap();
+        assert!(rk.0 == viewing_key.rk(ar, params).into());
+
+        let sighash = [7u8; 32];
+        let mut msg = [0u8; 64];
+        rk.write(&mut msg[..32]).unwrap();
+        msg[32..].copy_from_slice(&sighash);
+
+        let signing_sets = [vec![1, 2], vec![3, 1], vec![2, 3], vec![1, 2, 3]];
+        for (session_id, signers) in signing_sets.iter().enumerate() {
+            let signing: Vec<_> = signers.iter().map(|i| &shares[*i as usize - 1]).collect();
+            let session_id = session_id as u64;
+            let sig = sign(
+                &signing, session_id, signers, &rk, ar, &sighash, rng, params,
+            );
+            assert!(rk.verify(&msg, &sig, p_g, params));
+        }
+
+        // Signing with the wrong signing set fails
+        let sig = sign(
+            &[&shares[0], &shares[1]],
+            4,
+            &[1, 2, 3],
+            &rk,
+            ar,
+            &sighash,
+            rng,
+            params,
+        );
+        assert!(!rk.verify(&msg, &sig, p_g, params));
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+
+        let ask = rng.gen();
+        assert_eq!(
+            split_key::<Bls12, _>(ask, 0, 3, rng, params).err(),
+            Some(ThresholdError::InvalidThreshold)
+        );
+        assert_eq!(
+            split_key::<Bls12, _>(ask, 4, 3, rng, params).err(),
+            Some(ThresholdError::InvalidThreshold)
+        );
+
+        let (shares, _) = split_key::<Bls12, _>(ask, 2, 3, rng, params).unwrap();
+        assert_eq!(shares[1].index(), 2);
+        assert_eq!(shares[1].threshold(), 2);
+        assert_eq!(
+            shares[0].start_session(0, &[], &[7; 32], rng, params).err(),
+            Some(ThresholdError::NotEnoughSigners)
+        );
+        assert_eq!(
+            shares[0]
+                .start_session(0, &[1], &[7; 32], rng, params)
+                .err(),
+            Some(ThresholdError::NotEnoughSigners)
+        );
+        assert_eq!(
+            shares[0]
+                .start_session(0, &[1, 1], &[7; 32], rng, params)
+                .err(),
+            Some(ThresholdError::InvalidSigners)
+        );
+        assert_eq!(
+            shares[0]
+                .start_session(0, &[0, 1], &[7; 32], rng, params)
+                .err(),
+            Some(ThresholdError::InvalidSigners)
+        );
+        assert_eq!(
+            shares[0]
+                .start_session(0, &[2, 3], &[7; 32], rng, params)
+                .err(),
+            Some(ThresholdError::MissingOwnIndex)
+        );
+    }
+
+    #[test]
+    fn verify_shares() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+
+        let ask = rng.gen();
+        let (shares, commitments) = split_key::<Bls12, _>(ask, 3, 5, rng, params).unwrap();
+        assert_eq!(commitments.len(), 3);
+        assert!(commitments[0] == *shares[0].ak());
+        for share in &shares {
+            assert!(share.verify(&commitments, params));
+        }
+
+        // A share that is not on the committed polynomial
+        let mut share = shares[0].share;
+        share.add_assign(&<Bls12 as JubjubEngine>::Fs::one());
+        let bad_share = KeyShare {
+            index: 1,
+            threshold: 3,
+            share,
+            ak: shares[0].ak().clone(),
+        };
+        assert!(!bad_share.verify(&commitments, params));
+
+        // Commitments to another polynomial for the same key, or of the
+        // wrong degree
+        let (_, other) = split_key::<Bls12, _>(ask, 3, 5, rng, params).unwrap();
+        assert!(!shares[0].verify(&other, params));
+        assert!(!shares[0].verify(&commitments[..2], params));
+        assert!(!shares[0].verify(&[], params));
+    }
+
+    #[test]
+    fn share_encoding() {
+        let rng = &mut XorShiftRng::from_seed([0x3dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+        let params = &JubjubBls12::new();
+
+        let (shares, _) = split_key::<Bls12, _>(rng.gen(), 2, 3, rng, params).unwrap();
+        let mut encoded = vec![];
+        shares[2].write(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), 4 + 4 + 32 + 32);
+
+        let decoded = KeyShare::<Bls12>::read(&encoded[..], params).unwrap();
+        assert_eq!(decoded.index(), 3);
+        assert_eq!(decoded.threshold(), 2);
+        assert_eq!(decoded.share, shares[2].share);
+        assert!(decoded.ak() == shares[2].ak());
+
+        let reject = |offset: usize, bytes: &[u8]| {
+            let mut bad = encoded.clone();
+            bad[offset..offset + bytes.len()].copy_from_slice(bytes);
+            KeyShare::<Bls12>::read(&bad[..], params).is_err()
+        };
+
+        // A zero index or threshold
+        assert!(reject(0, &[0; 4]));
+        assert!(reject(4, &[0; 4]));
+
+        // A share that is not in the field
+        assert!(reject(8, &[0xff; 32]));
+
+        // An ak that is not a point, or is of small order
+        let mut small_order = [0u8; 32];
+        let mut y = Fr::one();
+        y.negate();
+        y.into_repr().write_le(&mut small_order[..]).unwrap();
+        assert!(reject(40, &[0xff; 32]));
+        assert!(reject(40, &small_order));
+
+        // A truncated encoding
+        assert!(KeyShare::<Bls12>::read(&encoded[..71], params).is_err());
+    }
+}
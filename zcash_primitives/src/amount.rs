@@ -0,0 +1,402 @@
+//! Monetary amounts, denominated in zatoshis.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// The number of zatoshis in one ZEC.
+pub const COIN: i64 = 1_0000_0000;
+
+/// The maximum number of zatoshis that can ever exist.
+pub const MAX_MONEY: i64 = 21_000_000 * COIN;
+
+/// The number of decimal places in a ZEC amount.
+const DECIMALS: usize = 8;
+
+/// A type-safe representation of some quantity of Zcash.
+///
+/// An Amount can only be constructed from an integer that is within the valid monetary
+/// range of `{-MAX_MONEY..MAX_MONEY}` (where `MAX_MONEY` = 21,000,000 × 10⁸ zatoshis).
+/// Negative amounts arise as value balances; use [`Amount::from_nonnegative_i64`] or
+/// [`Amount::from_u64`] where a negative amount would be an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i64);
+
+impl Amount {
+    /// Returns a zero-valued Amount.
+    pub fn zero() -> Self {
+        Amount(0)
+    }
+
+    /// Creates an Amount from an i64.
+    ///
+    /// Returns an error if the amount is outside the range `{-MAX_MONEY..MAX_MONEY}`.
+    pub fn from_i64(amount: i64) -> Result<Self, ()> {
+        if -MAX_MONEY <= amount && amount <= MAX_MONEY {
+            Ok(Amount(amount))
+        } else {
+            Err(())
+        }
+    }
+
+    /// Creates a non-negative Amount from an i64.
+    ///
+    /// Returns an error if the amount is outside the range `{0..MAX_MONEY}`.
+    pub fn from_nonnegative_i64(amount: i64) -> Result<Self, ()> {
+        if 0 <= amount && amount <= MAX_MONEY {
+            Ok(Amount(amount))
+        } else {
+            Err(())
+        }
+    }
+
+    /// Creates a non-negative Amount from a u64.
+    ///
+    /// Returns an error if the amount is outside the range `{0..MAX_MONEY}`.
+    pub fn from_u64(amount: u64) -> Result<Self, ()> {
+        if amount <= MAX_MONEY as u64 {
+            Ok(Amount(amount as i64))
+        } else {
+            Err(())
+        }
+    }
+
+    /// Returns `true` if `self` is positive and `false` if the Amount is zero or
+    /// negative.
+    pub fn is_positive(&self) -> bool {
+        self.0.is_positive()
+    }
+
+    /// Returns `true` if `self` is negative and `false` if the Amount is zero or
+    /// positive.
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+}
+
+impl From<Amount> for i64 {
+    fn from(amount: Amount) -> i64 {
+        amount.0
+    }
+}
+
+impl Add<Amount> for Amount {
+    type Output = Option<Amount>;
+
+    fn add(self, rhs: Amount) -> Option<Amount> {
+        Amount::from_i64(self.0 + rhs.0).ok()
+    }
+}
+
+impl Sub<Amount> for Amount {
+    type Output = Option<Amount>;
+
+    fn sub(self, rhs: Amount) -> Option<Amount> {
+        Amount::from_i64(self.0 - rhs.0).ok()
+    }
+}
+
+impl Neg for Amount {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Amount(-self.0)
+    }
+}
+
+impl Sum<Amount> for Option<Amount> {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Self {
+        iter.fold(Some(Amount::zero()), |acc, a| acc? + a)
+    }
+}
+
+/// Formats the amount in ZEC, with all eight decimal places.
+///
+/// Use `i64::from(amount)` for the value in zatoshis.
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let abs = self.0.abs();
+        write!(f, "{}{}.{:08}", sign, abs / COIN, abs % COIN)
+    }
+}
+
+/// A type-safe representation of some non-negative quantity of Zcash, such as
+/// a note value or a transparent output.
+///
+/// A NonNegativeAmount can only be constructed from an integer that is within the range
+/// `{0..MAX_MONEY}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonNegativeAmount(u64);
+
+impl NonNegativeAmount {
+    /// Returns a zero-valued NonNegativeAmount.
+    pub fn zero() -> Self {
+        NonNegativeAmount(0)
+    }
+
+    /// Creates a NonNegativeAmount from a u64.
+    ///
+    /// Returns an error if the amount is outside the range `{0..MAX_MONEY}`.
+    pub fn from_u64(amount: u64) -> Result<Self, ()> {
+        if amount <= MAX_MONEY as u64 {
+            Ok(NonNegativeAmount(amount))
+        } else {
+            Err(())
+        }
+    }
+
+    /// Creates a NonNegativeAmount from an i64.
+    ///
+    /// Returns an error if the amount is outside the range `{0..MAX_MONEY}`.
+    pub fn from_nonnegative_i64(amount: i64) -> Result<Self, ()> {
+        if 0 <= amount && amount <= MAX_MONEY {
+            Ok(NonNegativeAmount(amount as u64))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl From<NonNegativeAmount> for Amount {
+    fn from(amount: NonNegativeAmount) -> Amount {
+        Amount(amount.0 as i64)
+    }
+}
+
+impl From<NonNegativeAmount> for u64 {
+    fn from(amount: NonNegativeAmount) -> u64 {
+        amount.0
+    }
+}
+
+/// Returns an error if the amount is negative.
+impl TryFrom<Amount> for NonNegativeAmount {
+    type Error = ();
+
+    fn try_from(amount: Amount) -> Result<Self, ()> {
+        NonNegativeAmount::from_nonnegative_i64(amount.0)
+    }
+}
+
+impl Add<NonNegativeAmount> for NonNegativeAmount {
+    type Output = Option<NonNegativeAmount>;
+
+    fn add(self, rhs: NonNegativeAmount) -> Option<NonNegativeAmount> {
+        NonNegativeAmount::from_u64(self.0 + rhs.0).ok()
+    }
+}
+
+impl Sub<NonNegativeAmount> for NonNegativeAmount {
+    type Output = Option<NonNegativeAmount>;
+
+    fn sub(self, rhs: NonNegativeAmount) -> Option<NonNegativeAmount> {
+        self.0.checked_sub(rhs.0).map(NonNegativeAmount)
+    }
+}
+
+impl Sum<NonNegativeAmount> for Option<NonNegativeAmount> {
+    fn sum<I: Iterator<Item = NonNegativeAmount>>(iter: I) -> Self {
+        iter.fold(Some(NonNegativeAmount::zero()), |acc, a| acc? + a)
+    }
+}
+
+/// Formats the amount in ZEC, with all eight decimal places.
+impl fmt::Display for NonNegativeAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Amount::from(*self).fmt(f)
+    }
+}
+
+/// Errors that can occur when parsing a decimal ZEC amount.
+#[derive(Debug, PartialEq)]
+pub enum ParseAmountError {
+    /// The string was not a decimal number.
+    InvalidFormat,
+    /// The amount had more than eight decimal places.
+    TooPrecise,
+    /// The amount was outside the range `{-MAX_MONEY..MAX_MONEY}`.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseAmountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseAmountError::InvalidFormat => write!(f, "invalid ZEC amount"),
+            ParseAmountError::TooPrecise => write!(f, "ZEC amount has more than 8 decimals"),
+            ParseAmountError::OutOfRange => write!(f, "ZEC amount is out of range"),
+        }
+    }
+}
+
+/// Parses a decimal amount of ZEC, such as `"0.5"` or `"-21000000.00000000"`.
+impl FromStr for Amount {
+    type Err = ParseAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = if s.starts_with('-') {
+            (true, &s[1..])
+        } else {
+            (false, s)
+        };
+
+        let (whole, frac) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+
+        if whole.is_empty()
+            || !whole.bytes().all(|b| b.is_ascii_digit())
+            || !frac.bytes().all(|b| b.is_ascii_digit())
+            || s.ends_with('.')
+        {
+            return Err(ParseAmountError::InvalidFormat);
+        }
+        if frac.len() > DECIMALS {
+            return Err(ParseAmountError::TooPrecise);
+        }
+
+        // Anything longer than this cannot be below MAX_MONEY
+        if whole.bytes().skip_while(|&b| b == b'0').count() > 8 {
+            return Err(ParseAmountError::OutOfRange);
+        }
+
+        let whole: i64 = whole.parse().map_err(|_| ParseAmountError::InvalidFormat)?;
+        let mut zats = whole * COIN;
+        if !frac.is_empty() {
+            let scale = 10i64.pow((DECIMALS - frac.len()) as u32);
+            let frac: i64 = frac.parse().map_err(|_| ParseAmountError::InvalidFormat)?;
+            zats += frac * scale;
+        }
+
+        Amount::from_i64(if negative { -zats } else { zats })
+            .map_err(|_| ParseAmountError::OutOfRange)
+    }
+}
+
+/// Parses a non-negative decimal amount of ZEC, such as `"0.5"`.
+impl FromStr for NonNegativeAmount {
+    type Err = ParseAmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let amount: Amount = s.parse()?;
+        NonNegativeAmount::try_from(amount).map_err(|_| ParseAmountError::OutOfRange)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::{Amount, NonNegativeAmount, ParseAmountError, MAX_MONEY};
+
+    #[test]
+    fn amount_in_range() {
+        assert_eq!(Amount::from_i64(MAX_MONEY), Ok(Amount(MAX_MONEY)));
+        assert_eq!(Amount::from_i64(-MAX_MONEY), Ok(Amount(-MAX_MONEY)));
+        assert!(Amount::from_i64(MAX_MONEY + 1).is_err());
+        assert!(Amount::from_i64(-MAX_MONEY - 1).is_err());
+
+        assert!(Amount::from_nonnegative_i64(0).is_ok());
+        assert!(Amount::from_nonnegative_i64(-1).is_err());
+        assert!(Amount::from_u64(MAX_MONEY as u64).is_ok());
+        assert!(Amount::from_u64(MAX_MONEY as u64 + 1).is_err());
+    }
+
+    #[test]
+    fn nonnegative_amount_in_range() {
+        assert_eq!(
+            NonNegativeAmount::from_u64(MAX_MONEY as u64),
+            Ok(NonNegativeAmount(MAX_MONEY as u64))
+        );
+        assert!(NonNegativeAmount::from_u64(MAX_MONEY as u64 + 1).is_err());
+        assert!(NonNegativeAmount::from_nonnegative_i64(0).is_ok());
+        assert!(NonNegativeAmount::from_nonnegative_i64(-1).is_err());
+        assert!(NonNegativeAmount::from_nonnegative_i64(MAX_MONEY + 1).is_err());
+
+        assert_eq!(
+            NonNegativeAmount::try_from(Amount(5)),
+            Ok(NonNegativeAmount(5))
+        );
+        assert!(NonNegativeAmount::try_from(Amount(-5)).is_err());
+        assert_eq!(Amount::from(NonNegativeAmount(5)), Amount(5));
+
+        let max = NonNegativeAmount(MAX_MONEY as u64);
+        let one = NonNegativeAmount(1);
+        assert_eq!(max + one, None);
+        assert_eq!(NonNegativeAmount::zero() - one, None);
+        assert_eq!(max - one, Some(NonNegativeAmount(MAX_MONEY as u64 - 1)));
+        assert_eq!(
+            vec![max, one]
+                .into_iter()
+                .sum::<Option<NonNegativeAmount>>(),
+            None
+        );
+
+        assert_eq!(NonNegativeAmount(150_000_000).to_string(), "1.50000000");
+        assert_eq!("1.5".parse(), Ok(NonNegativeAmount(150_000_000)));
+        assert_eq!(
+            "-0.00000001".parse::<NonNegativeAmount>(),
+            Err(ParseAmountError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let max = Amount::from_i64(MAX_MONEY).unwrap();
+        let one = Amount::from_i64(1).unwrap();
+        assert_eq!(max + one, None);
+        assert_eq!(-max - one, None);
+        assert_eq!(max - one, Some(Amount(MAX_MONEY - 1)));
+        assert_eq!(
+            vec![one, one, one].into_iter().sum::<Option<Amount>>(),
+            Some(Amount(3))
+        );
+        assert_eq!(vec![max, one].into_iter().sum::<Option<Amount>>(), None);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Amount(0).to_string(), "0.00000000");
+        assert_eq!(Amount(1).to_string(), "0.00000001");
+        assert_eq!(Amount(-150_000_000).to_string(), "-1.50000000");
+        assert_eq!(Amount(MAX_MONEY).to_string(), "21000000.00000000");
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("0".parse(), Ok(Amount(0)));
+        assert_eq!("1.5".parse(), Ok(Amount(150_000_000)));
+        assert_eq!("-0.00000001".parse(), Ok(Amount(-1)));
+        assert_eq!("21000000.00000000".parse(), Ok(Amount(MAX_MONEY)));
+        assert_eq!("0021000000".parse(), Ok(Amount(MAX_MONEY)));
+
+        for s in &["", ".", "1.", ".5", "-", "+1", "1.2.3", "1e8", " 1", "--1"] {
+            assert_eq!(
+                s.parse::<Amount>(),
+                Err(ParseAmountError::InvalidFormat),
+                "{:?}",
+                s
+            );
+        }
+        assert_eq!(
+            "0.000000001".parse::<Amount>(),
+            Err(ParseAmountError::TooPrecise)
+        );
+        assert_eq!(
+            "21000000.00000001".parse::<Amount>(),
+            Err(ParseAmountError::OutOfRange)
+        );
+        assert_eq!(
+            "100000000000000000000".parse::<Amount>(),
+            Err(ParseAmountError::OutOfRange)
+        );
+
+        for &zats in &[0, 1, -1, 123_456_789, MAX_MONEY, -MAX_MONEY] {
+            let amount = Amount(zats);
+            assert_eq!(amount.to_string().parse(), Ok(amount));
+        }
+    }
+}
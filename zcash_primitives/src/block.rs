@@ -0,0 +1,249 @@
+//! Structs and methods for handling Zcash block headers.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+
+use equihash;
+use pow::U256;
+use serialize::{read_compact, write_compact};
+
+/// The Equihash `n` parameter used on mainnet and testnet.
+pub const EQUIHASH_N: u32 = 200;
+
+/// The Equihash `k` parameter used on mainnet and testnet.
+pub const EQUIHASH_K: u32 = 9;
+
+/// The largest Equihash solution we will read, in bytes. This is the size of a
+/// (200, 9) solution.
+const MAX_SOLUTION_SIZE: u64 = 1344;
+
+/// A block hash, in its internal (little-endian) byte order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockHash(pub [u8; 32]);
+
+/// Formats the hash in the conventional byte-reversed hex form used by block
+/// explorers and RPC interfaces.
+impl fmt::Display for BlockHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in self.0.iter().rev() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// A Zcash block header.
+///
+/// The header's hash is computed when it is read or frozen, and cached.
+#[derive(Clone, Debug)]
+pub struct BlockHeader {
+    hash: BlockHash,
+    data: BlockHeaderData,
+}
+
+impl Deref for BlockHeader {
+    type Target = BlockHeaderData;
+
+    fn deref(&self) -> &BlockHeaderData {
+        &self.data
+    }
+}
+
+/// The fields of a Zcash block header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockHeaderData {
+    pub version: i32,
+    pub prev_block: BlockHash,
+    pub merkle_root: [u8; 32],
+    /// The root of the Sapling note commitment tree after this block. This
+    /// field was reserved (and zero) before Sapling activation.
+    pub final_sapling_root: [u8; 32],
+    pub time: u32,
+    /// The proof-of-work target, in compact form.
+    pub bits: u32,
+    pub nonce: [u8; 32],
+    pub solution: Vec<u8>,
+}
+
+impl BlockHeaderData {
+    /// Computes the hash of this header and returns the resulting
+    /// [`BlockHeader`].
+    pub fn freeze(self) -> io::Result<BlockHeader> {
+        BlockHeader::from_data(self)
+    }
+
+    fn write_equihash_input<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_i32::<LittleEndian>(self.version)?;
+        writer.write_all(&self.prev_block.0)?;
+        writer.write_all(&self.merkle_root)?;
+        writer.write_all(&self.final_sapling_root)?;
+        writer.write_u32::<LittleEndian>(self.time)?;
+        writer.write_u32::<LittleEndian>(self.bits)
+    }
+}
+
+impl BlockHeader {
+    fn from_data(data: BlockHeaderData) -> io::Result<Self> {
+        let mut header = BlockHeader {
+            data,
+            hash: BlockHash([0; 32]),
+        };
+        let mut raw = vec![];
+        header.write(&mut raw)?;
+        header.hash = BlockHash(sha256d(&raw));
+        Ok(header)
+    }
+
+    /// Returns the hash of this header.
+    pub fn hash(&self) -> BlockHash {
+        self.hash
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let version = reader.read_i32::<LittleEndian>()?;
+
+        let mut prev_block = BlockHash([0; 32]);
+        reader.read_exact(&mut prev_block.0)?;
+
+        let mut merkle_root = [0; 32];
+        reader.read_exact(&mut merkle_root)?;
+
+        let mut final_sapling_root = [0; 32];
+        reader.read_exact(&mut final_sapling_root)?;
+
+        let time = reader.read_u32::<LittleEndian>()?;
+        let bits = reader.read_u32::<LittleEndian>()?;
+
+        let mut nonce = [0; 32];
+        reader.read_exact(&mut nonce)?;
+
+        let solution_len = read_compact(&mut reader)?;
+        if solution_len > MAX_SOLUTION_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Equihash solution is too large",
+            ));
+        }
+        let mut solution = vec![0; solution_len as usize];
+        reader.read_exact(&mut solution)?;
+
+        BlockHeader::from_data(BlockHeaderData {
+            version,
+            prev_block,
+            merkle_root,
+            final_sapling_root,
+            time,
+            bits,
+            nonce,
+            solution,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.write_equihash_input(&mut writer)?;
+        writer.write_all(&self.nonce)?;
+        write_compact(&mut writer, self.solution.len() as u64)?;
+        writer.write_all(&self.solution)
+    }
+
+    /// Returns true if this header's Equihash solution is valid for the given
+    /// parameters. Mainnet and testnet use [`EQUIHASH_N`] and [`EQUIHASH_K`].
+    pub fn is_valid_solution(&self, n: u32, k: u32) -> bool {
+        // The solution is 2^k indices, each (n / (k + 1) + 1) bits long
+        let index_bits = (n / (k + 1) + 1) as usize;
+        if self.solution.len() != (index_bits << k) / 8 {
+            return false;
+        }
+
+        let mut input = vec![];
+        self.write_equihash_input(&mut input)
+            .expect("writing to a Vec should not fail");
+        equihash::is_valid_solution(n, k, &input, &self.nonce, &self.solution)
+    }
+
+    /// Returns true if this header's hash is no greater than the target
+    /// encoded in `bits`. A target that is zero, negative or overflows is
+    /// never met.
+    pub fn hash_meets_target(&self) -> bool {
+        match U256::from_compact(self.bits) {
+            Some(target) => U256::from_le_bytes(&self.hash.0) <= target,
+            None => false,
+        }
+    }
+}
+
+/// SHA-256d, the double SHA-256 hash used for block hashes and checksums.
+pub(crate) fn sha256d(data: &[u8]) -> [u8; 32] {
+    let mut result = [0; 32];
+    result.copy_from_slice(&Sha256::digest(&Sha256::digest(data)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockHeader, BlockHeaderData};
+
+    // A header using the regtest Equihash parameters (48, 5), with a solution
+    // found by a reference solver.
+    const HEADER_48_5: [u8; 177] = [
+        0x04, 0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a,
+        0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+        0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28,
+        0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+        0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f, 0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46,
+        0x47, 0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e, 0x4f, 0x50, 0x51, 0x52, 0x53, 0x54, 0x55,
+        0x56, 0x57, 0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d, 0x5e, 0x5f, 0x00, 0x89, 0xca, 0x5b, 0x0f,
+        0x0f, 0x0f, 0x20, 0x29, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x24, 0x08, 0xf7, 0x0e, 0x94, 0x03, 0x64, 0xf2, 0x55, 0xf8,
+        0x94, 0xe2, 0x6f, 0xd8, 0x8a, 0x5f, 0x2f, 0x03, 0xfe, 0x0f, 0xf6, 0xce, 0xef, 0xb4, 0x74,
+        0x72, 0x45, 0x93, 0x17, 0x98, 0xcc, 0x8d, 0x46, 0x63, 0x8e, 0x8d, 0x67,
+    ];
+
+    #[test]
+    fn header_read_write() {
+        let header = BlockHeader::read(&HEADER_48_5[..]).unwrap();
+        assert_eq!(header.version, 4);
+        assert_eq!(header.time, 0x5bca_8900);
+        assert_eq!(header.bits, 0x200f_0f0f);
+        assert_eq!(header.solution.len(), 36);
+        assert_eq!(
+            header.hash().to_string(),
+            "015ecbd8705b3027222fcc83279bb104a6d56a02ef42527a5f34bc31c9983abc"
+        );
+
+        let mut encoded = vec![];
+        header.write(&mut encoded).unwrap();
+        assert_eq!(&encoded[..], &HEADER_48_5[..]);
+
+        // Truncated headers are rejected
+        assert!(BlockHeader::read(&HEADER_48_5[..176]).is_err());
+    }
+
+    #[test]
+    fn header_solution() {
+        let header = BlockHeader::read(&HEADER_48_5[..]).unwrap();
+        assert!(header.is_valid_solution(48, 5));
+        assert!(header.hash_meets_target());
+
+        // The solution has the wrong length for other parameters
+        assert!(!header.is_valid_solution(200, 9));
+
+        // Changing any committed field invalidates the solution
+        let data: &BlockHeaderData = &header;
+        let mut modified = data.clone();
+        modified.time += 1;
+        assert!(!modified.freeze().unwrap().is_valid_solution(48, 5));
+
+        let mut modified = data.clone();
+        modified.nonce[31] ^= 1;
+        assert!(!modified.freeze().unwrap().is_valid_solution(48, 5));
+
+        let mut modified = data.clone();
+        modified.solution[0] ^= 1;
+        assert!(!modified.freeze().unwrap().is_valid_solution(48, 5));
+    }
+}
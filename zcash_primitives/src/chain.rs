@@ -0,0 +1,686 @@
+//! Header-chain validation and best-chain selection for light clients.
+//!
+//! [`HeaderChain`] checks the proof of work, difficulty adjustment and parent
+//! links of the headers it is given, so that a light client does not need to
+//! trust the server it downloads them from to tell it which chain is best.
+
+use std::fmt;
+
+use block::{BlockHash, BlockHeader, EQUIHASH_K, EQUIHASH_N};
+use consensus::Network;
+use pow::U256;
+
+/// The number of blocks whose timestamps are used to compute the median time
+/// past.
+const MEDIAN_TIME_SPAN: u32 = 11;
+
+/// Proof-of-work consensus parameters for a network.
+#[derive(Clone, Debug)]
+pub struct ChainParams {
+    equihash_n: u32,
+    equihash_k: u32,
+    pow_limit: U256,
+    averaging_window: u32,
+    max_adjust_down: u32,
+    max_adjust_up: u32,
+    target_spacing: u32,
+    /// The height after which a testnet block may use the minimum difficulty
+    /// if it is more than six block intervals after its parent.
+    min_difficulty_after: Option<u32>,
+}
+
+impl ChainParams {
+    pub fn for_network(network: Network) -> Self {
+        let (pow_limit, min_difficulty_after) = match network {
+            Network::MainNetwork => (!U256::zero() >> 13, None),
+            Network::TestNetwork => (!U256::zero() >> 5, Some(299_187)),
+        };
+
+        ChainParams {
+            equihash_n: EQUIHASH_N,
+            equihash_k: EQUIHASH_K,
+            pow_limit,
+            averaging_window: 17,
+            max_adjust_down: 32,
+            max_adjust_up: 16,
+            target_spacing: 150,
+            min_difficulty_after,
+        }
+    }
+
+    fn averaging_window_timespan(&self) -> i64 {
+        (self.averaging_window * self.target_spacing) as i64
+    }
+
+    fn min_actual_timespan(&self) -> i64 {
+        self.averaging_window_timespan() * (100 - self.max_adjust_up as i64) / 100
+    }
+
+    fn max_actual_timespan(&self) -> i64 {
+        self.averaging_window_timespan() * (100 + self.max_adjust_down as i64) / 100
+    }
+}
+
+/// The reasons a header can be rejected by a [`HeaderChain`].
+#[derive(Debug, PartialEq)]
+pub enum ChainError {
+    /// No headers were provided.
+    Empty,
+    /// The first header's parent is not in the chain.
+    UnknownParent(BlockHash),
+    /// The branch would replace a header that was trusted when the chain was
+    /// created.
+    ForkBeforeCheckpoint(u32),
+    /// The header at this height does not build on the header before it.
+    InvalidParent(u32),
+    /// The header at this height has an invalid Equihash solution.
+    InvalidSolution(u32),
+    /// The header at this height does not meet its own target.
+    HighHash(u32),
+    /// The header at this height has a target that differs from the one
+    /// required by the difficulty adjustment.
+    UnexpectedTarget {
+        height: u32,
+        expected: u32,
+        actual: u32,
+    },
+    /// The header at this height is not later than the median time past.
+    TimeTooOld(u32),
+    /// The chain does not go back far enough to check the header at this
+    /// height.
+    InsufficientHistory(u32),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChainError::Empty => write!(f, "no headers provided"),
+            ChainError::UnknownParent(ref hash) => write!(f, "unknown parent block {}", hash),
+            ChainError::ForkBeforeCheckpoint(h) => write!(f, "fork below checkpoint at {}", h),
+            ChainError::InvalidParent(h) => write!(f, "header {} has the wrong parent", h),
+            ChainError::InvalidSolution(h) => {
+                write!(f, "header {} has an invalid Equihash solution", h)
+            }
+            ChainError::HighHash(h) => write!(f, "header {} does not meet its target", h),
+            ChainError::UnexpectedTarget {
+                height,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "header {} has target {:08x}, expected {:08x}",
+                height, actual, expected
+            ),
+            ChainError::TimeTooOld(h) => write!(f, "header {} is too early", h),
+            ChainError::InsufficientHistory(h) => {
+                write!(f, "not enough history to check header {}", h)
+            }
+        }
+    }
+}
+
+/// How a [`HeaderChain`] changed after connecting a branch.
+#[derive(Debug, PartialEq)]
+pub enum ChainUpdate {
+    /// The branch was appended to the previous tip.
+    Extended,
+    /// The branch has more work than the previous best chain, and replaced
+    /// every header above `fork_height`. `disconnected` lists the hashes of the
+    /// replaced headers, from the lowest.
+    Reorganized {
+        fork_height: u32,
+        disconnected: Vec<BlockHash>,
+    },
+    /// The branch is valid but does not have more work than the best chain,
+    /// so it was not kept.
+    NotBest,
+}
+
+struct ChainEntry {
+    header: BlockHeader,
+    /// Total work of the chain from the first header up to this one.
+    chain_work: U256,
+}
+
+/// The best chain of block headers above a trusted starting point.
+pub struct HeaderChain {
+    params: ChainParams,
+    start_height: u32,
+    /// The number of entries that were trusted at creation.
+    checkpoint_len: usize,
+    entries: Vec<ChainEntry>,
+}
+
+/// Headers of a candidate chain, indexed by height.
+struct Candidate<'a> {
+    start_height: u32,
+    headers: Vec<&'a BlockHeader>,
+}
+
+impl<'a> Candidate<'a> {
+    fn get(&self, height: i64) -> Result<Option<&'a BlockHeader>, ()> {
+        if height < 0 {
+            // Before genesis
+            Ok(None)
+        } else if height < self.start_height as i64 {
+            Err(())
+        } else {
+            Ok(self
+                .headers
+                .get((height - self.start_height as i64) as usize)
+                .cloned())
+        }
+    }
+
+    fn median_time_past(&self, height: u32) -> Result<u32, ()> {
+        let mut times = vec![];
+        for i in 0..MEDIAN_TIME_SPAN {
+            match self.get(height as i64 - i as i64)? {
+                Some(header) => times.push(header.time),
+                None => break,
+            }
+        }
+        times.sort();
+        Ok(times[times.len() / 2])
+    }
+}
+
+impl HeaderChain {
+    /// Starts a chain from `checkpoint`, a list of consecutive headers ending
+    /// at a trusted block, the first of which is at `start_height`.
+    ///
+    /// Only the links between the checkpoint headers are checked. Checking the
+    /// difficulty adjustment of a header needs the 28 headers before it, so
+    /// the checkpoint should be at least that long unless it starts at the
+    /// genesis block.
+    pub fn new(
+        params: ChainParams,
+        start_height: u32,
+        checkpoint: Vec<BlockHeader>,
+    ) -> Result<Self, ChainError> {
+        if checkpoint.is_empty() {
+            return Err(ChainError::Empty);
+        }
+
+        let mut entries: Vec<ChainEntry> = Vec::with_capacity(checkpoint.len());
+        for (i, header) in checkpoint.into_iter().enumerate() {
+            let chain_work = match entries.last() {
+                Some(parent) => {
+                    if header.prev_block != parent.header.hash() {
+                        return Err(ChainError::InvalidParent(start_height + i as u32));
+                    }
+                    parent.chain_work
+                }
+                None => U256::zero(),
+            };
+            let work = header_work(&header)
+                .ok_or_else(|| ChainError::HighHash(start_height + i as u32))?;
+
+            entries.push(ChainEntry {
+                header,
+                chain_work: chain_work + work,
+            });
+        }
+
+        Ok(HeaderChain {
+            params,
+            start_height,
+            checkpoint_len: entries.len(),
+            entries,
+        })
+    }
+
+    /// Returns the height of the best chain's tip.
+    pub fn tip_height(&self) -> u32 {
+        self.start_height + self.entries.len() as u32 - 1
+    }
+
+    /// Returns the tip of the best chain.
+    pub fn tip(&self) -> &BlockHeader {
+        &self.entries.last().expect("chain is never empty").header
+    }
+
+    /// Returns the header at the given height in the best chain.
+    pub fn get(&self, height: u32) -> Option<&BlockHeader> {
+        if height < self.start_height {
+            return None;
+        }
+        self.entries
+            .get((height - self.start_height) as usize)
+            .map(|e| &e.header)
+    }
+
+    /// Returns the height of the given block, if it is in the best chain.
+    pub fn height_of(&self, hash: &BlockHash) -> Option<u32> {
+        self.entries
+            .iter()
+            .rposition(|e| e.header.hash() == *hash)
+            .map(|i| self.start_height + i as u32)
+    }
+
+    /// Returns the total work of the best chain since the first checkpoint
+    /// header, as a little-endian 256-bit integer.
+    pub fn chain_work(&self) -> [u8; 32] {
+        self.entries
+            .last()
+            .expect("chain is never empty")
+            .chain_work
+            .to_le_bytes()
+    }
+
+    /// Validates `branch`, a list of consecutive headers whose first header
+    /// builds on a block in the best chain, and makes it part of the best
+    /// chain if that gives more total work.
+    ///
+    /// Nothing is changed if any header in the branch is invalid.
+    pub fn connect(&mut self, branch: Vec<BlockHeader>) -> Result<ChainUpdate, ChainError> {
+        let fork_height = match branch.first() {
+            Some(first) => self
+                .height_of(&first.prev_block)
+                .ok_or(ChainError::UnknownParent(first.prev_block))?,
+            None => return Err(ChainError::Empty),
+        };
+        let fork_index = (fork_height - self.start_height) as usize;
+        if fork_index + 1 < self.checkpoint_len {
+            return Err(ChainError::ForkBeforeCheckpoint(fork_height));
+        }
+
+        let mut chain_work = self.entries[fork_index].chain_work;
+        let mut works = Vec::with_capacity(branch.len());
+        {
+            let mut candidate = Candidate {
+                start_height: self.start_height,
+                headers: self.entries[..fork_index + 1]
+                    .iter()
+                    .map(|e| &e.header)
+                    .collect(),
+            };
+            for (i, header) in branch.iter().enumerate() {
+                let height = fork_height + 1 + i as u32;
+                let parent = candidate.headers[candidate.headers.len() - 1];
+                if header.prev_block != parent.hash() {
+                    return Err(ChainError::InvalidParent(height));
+                }
+                self.check_header(&candidate, header, height)?;
+
+                let work = header_work(header).ok_or(ChainError::HighHash(height))?;
+                chain_work = chain_work + work;
+                works.push(chain_work);
+                candidate.headers.push(header);
+            }
+        }
+
+        let update = if fork_index + 1 == self.entries.len() {
+            ChainUpdate::Extended
+        } else if chain_work
+            > self
+                .entries
+                .last()
+                .expect("chain is never empty")
+                .chain_work
+        {
+            ChainUpdate::Reorganized {
+                fork_height,
+                disconnected: self.entries[fork_index + 1..]
+                    .iter()
+                    .map(|e| e.header.hash())
+                    .collect(),
+            }
+        } else {
+            return Ok(ChainUpdate::NotBest);
+        };
+
+        self.entries.truncate(fork_index + 1);
+        for (header, chain_work) in branch.into_iter().zip(works) {
+            self.entries.push(ChainEntry { header, chain_work });
+        }
+
+        Ok(update)
+    }
+
+    /// Performs the checks on `header` that depend on its ancestors.
+    fn check_header(
+        &self,
+        candidate: &Candidate,
+        header: &BlockHeader,
+        height: u32,
+    ) -> Result<(), ChainError> {
+        if !header.is_valid_solution(self.params.equihash_n, self.params.equihash_k) {
+            return Err(ChainError::InvalidSolution(height));
+        }
+
+        let median_time_past = candidate
+            .median_time_past(height - 1)
+            .map_err(|_| ChainError::InsufficientHistory(height))?;
+        if header.time <= median_time_past {
+            return Err(ChainError::TimeTooOld(height));
+        }
+
+        let expected = self
+            .next_work_required(candidate, header, height)
+            .map_err(|_| ChainError::InsufficientHistory(height))?;
+        if header.bits != expected {
+            return Err(ChainError::UnexpectedTarget {
+                height,
+                expected,
+                actual: header.bits,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Computes the compact target required for `header`, following
+    /// `GetNextWorkRequired` in zcashd.
+    fn next_work_required(
+        &self,
+        candidate: &Candidate,
+        header: &BlockHeader,
+        height: u32,
+    ) -> Result<u32, ()> {
+        let params = &self.params;
+        let pow_limit = params.pow_limit.to_compact();
+        let last = candidate.get(height as i64 - 1)?.ok_or(())?;
+
+        if let Some(after) = params.min_difficulty_after {
+            if height > after && header.time > last.time + params.target_spacing * 6 {
+                return Ok(pow_limit);
+            }
+        }
+
+        // Find the first block in the averaging window, and the average of
+        // the targets within the window
+        let mut total = U256::zero();
+        for i in 0..params.averaging_window {
+            match candidate.get(height as i64 - 1 - i as i64)? {
+                Some(header) => total = total + U256::from_compact(header.bits).ok_or(())?,
+                // Not enough blocks since genesis
+                None => return Ok(pow_limit),
+            }
+        }
+        let first_height = height as i64 - 1 - params.averaging_window as i64;
+        if candidate.get(first_height)?.is_none() {
+            return Ok(pow_limit);
+        }
+        let average = total / params.averaging_window as u64;
+
+        // Limit the adjustment step, using the median time past to resist
+        // timestamp manipulation
+        let actual_timespan = candidate.median_time_past(height - 1)? as i64
+            - candidate.median_time_past(first_height as u32)? as i64;
+        let averaging_timespan = params.averaging_window_timespan();
+        let actual_timespan = averaging_timespan + (actual_timespan - averaging_timespan) / 4;
+        let actual_timespan = if actual_timespan < params.min_actual_timespan() {
+            params.min_actual_timespan()
+        } else if actual_timespan > params.max_actual_timespan() {
+            params.max_actual_timespan()
+        } else {
+            actual_timespan
+        };
+
+        let target = average / averaging_timespan as u64 * actual_timespan as u32;
+        if target > params.pow_limit {
+            Ok(pow_limit)
+        } else {
+            Ok(target.to_compact())
+        }
+    }
+}
+
+/// Returns the work represented by a header, or `None` if its hash does not
+/// meet its target.
+fn header_work(header: &BlockHeader) -> Option<U256> {
+    if header.hash_meets_target() {
+        U256::from_compact(header.bits).map(|target| target.work_for_target())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainError, ChainParams, ChainUpdate, HeaderChain};
+    use block::{BlockHeader, BlockHeaderData};
+    use pow::U256;
+
+    // Headers were mined with the parameters below by a reference
+    // implementation of the difficulty adjustment, with blocks every 60 seconds
+    // up to height 30 and every 100 seconds after.
+    // Heights 0 to 35.
+    const MAIN_CHAIN: &[&str] = &[
+        "0400000000000000000000000000000000000000000000000000000000000000000000002498c4cd987b16bdca8edb572c79e077433c77ea77b844557d156e7e324496f600000000000000000000000000000000000000000000000000000000000000000089ca5bffff07200e00000000000000000000000000000000000000000000000000000000000000240614a9ba36d762f5c80c89db76a21d11ff060a126c7bd85c62573c2d9f504c15965f51c4",
+        "04000000910a3402c4268fd4ce1c9a74a4a628afccb8b813cccd0385546af0a05fb8e602ef84cc413f0d23cd0c21137b8952ce454aa1cfd20c4fb95cb7cde7c4df89740300000000000000000000000000000000000000000000000000000000000000003c89ca5bffff07204700000000000000000000000000000000000000000000000000000000000000240b580b53a4259ae184363de555f5ddeea99d1ac411b2e66ebb6bb71d679750660715fd0f",
+        "04000000d6fc398f9a4b832fdc4b4affa93fa36fa60a2c96495f23782286273ec1bc0903ffa3dbd5016620c8dec9fe442f6b552e5d5e085e5f68cc3dbe631d2beabcdbcc00000000000000000000000000000000000000000000000000000000000000007889ca5bffff07200f000000000000000000000000000000000000000000000000000000000000002406da9b705329df83fe2a455fd1b3724d3bcc2fd5955a35d6f24f9b36e9a413e4facdd51c",
+        "040000006d6e703909b012403ccaaf500a2726927963300b9748ec5fd238b943c224a103b766151f422591e88b69e0cfc8e3026a55cef6d789d0745f2e52ce1a74d6ac110000000000000000000000000000000000000000000000000000000000000000b489ca5bffff07201c00000000000000000000000000000000000000000000000000000000000000240eea681e91354237bd49d1d6b5b6130ec1be313067b9043b813d1d3c35567c163f6eb5ee",
+        "040000009ecb4245db6988b11469174b294b4133fde5c74d9859bb099f8d6d7ffe4c7603fbcdc4c74c46baa719df11694c922c9c3b890478478b5771703f0ffa3d6f5f580000000000000000000000000000000000000000000000000000000000000000f089ca5bffff072016000000000000000000000000000000000000000000000000000000000000002404f291f273ec7361c818a976dde63e56c1cc09be8c9e10a780405c0d4caa17c1f468cce8",
+        "0400000034ccaf233f79fe5fd43f03ee684b33e0022ca4aa07c584314d7965b8ceb71607083ce5f4eba963331142b34dda73a12650f602fdae2cc45bace95a00bb85780700000000000000000000000000000000000000000000000000000000000000002c8aca5bffff07204300000000000000000000000000000000000000000000000000000000000000240447416592de9e87b31ccac7c767b3ea172c17759136275411f7dd3fdaa8de05661180c5",
+        "040000002231163f1b3bc42023bfe937efeffb5537b5b0f35674629263c345e9da01fd044e9d65f06fc05174ce51e018a79911fcf0199f194e7e8fe6ce6a6a81f7ce3b1a0000000000000000000000000000000000000000000000000000000000000000688aca5bffff07200b0000000000000000000000000000000000000000000000000000000000000024059f8db330dc7a77c008f2e1f351d4aa8de30bc34463c10bb0b8eb18a3097744b5fb51dc",
+        "04000000146643e8b17b0fe2959395ec645b596b1ce18225fb54714e0bb07e07626f9201689c462779b0e8a72dc5915b11ec31d8e62d6ab93d468da25eb7da56953e3c9f0000000000000000000000000000000000000000000000000000000000000000a48aca5bffff07200700000000000000000000000000000000000000000000000000000000000000240208357d6383b223a0130b6599f51382bbb40d40c8a4e28a1ab39239afd72e542712dfe1",
+        "04000000dbfde0dbb55543d7a5a3b18e3e6da679688a637244d43c8b6471d735c8c193047bf6f19a1eb2ac6aa61a20bd35a45857ca854d0cf6bbcc4a406e971ccca2aa4e0000000000000000000000000000000000000000000000000000000000000000e08aca5bffff07202f000000000000000000000000000000000000000000000000000000000000002408ca1e906854fe454a0b4d655b66a78fbdec1a7a974c7227328b884873d2f7e7fdeb65e7",
+        "040000003ab5235abfc94fee236707e1408454b35c41933fa6e41dc429c955e13d175001c5a41138954b0bd6d974ed2278e7909be64eef0907846e2d434a6a69189cfc5500000000000000000000000000000000000000000000000000000000000000001c8bca5bffff07201d000000000000000000000000000000000000000000000000000000000000002414decf7c12bb85fb9e3fcfdaf29633cd912e156759d324842f39b925b3757bf54ece57c7",
+        "04000000dd761146552c07e29148940a75a31a488d03127028c6d8aa4094bf30f544c4016403203dd5a0867eb14d104ee8a73730bd72dd9ad92e78d996a6dba0a5dcfc010000000000000000000000000000000000000000000000000000000000000000588bca5bffff07201c00000000000000000000000000000000000000000000000000000000000000240b1a54f8750731ed741c304c94f239b1dd8030d9e938646be74de8484fdbf6c675ee0b9a",
+        "040000000c6140eb8458cdf6d2ce4dce747e368fa362b29573dc5074f0cabb7cbb03c60206f77d7104338a91d12170a9b259840bdc7bb234a1624bb95e0943356bef9a760000000000000000000000000000000000000000000000000000000000000000948bca5bffff072041000000000000000000000000000000000000000000000000000000000000002417d413b644a3b201801c42d45512fdb6937825b0924fd455e9c1092d2b215634fa9f13af",
+        "04000000c04d9e7f2671aef1a0ba14341076531caf48ff9a56af4dcaebd7b86c1b09e302a97d6e533db2d2d84b73bc1204042b66dcd5aaba50cc200a753d072d87a0c6d30000000000000000000000000000000000000000000000000000000000000000d08bca5bffff072054000000000000000000000000000000000000000000000000000000000000002403a45f7031bbc58b3926d0905154e516dbcb06c9cd5e10abb0b3da36c76bd856d5564577",
+        "040000003f5ea2054db657ceaae6603d6e1db05f3aaac3c9924d761cdea8ef8198092d01b2c754459a8641e61342453a281e71ba6835e6933c67f020c05e4ea32a1893b000000000000000000000000000000000000000000000000000000000000000000c8cca5bffff07205d0000000000000000000000000000000000000000000000000000000000000024107fdcef0138d4c2c014981f14d17f0fa5dc10cf20f431eebd3fe239acd84ca5764b1596",
+        "04000000857962725670c96e831f831ca01b754e280e10408942e7d1870e84cd28672600bc2ffd62d8c14122777714c38cbbcc45655cb3dfaa97d78c6527e1e172ad9e760000000000000000000000000000000000000000000000000000000000000000488cca5bffff07205f000000000000000000000000000000000000000000000000000000000000002403a194ab7462d1498607ea429c453ae26dd936cb2ddd43c774f92f3bf1aadcc6b421f38b",
+        "040000000acb60039f391e583f14b41324a9d99e42030270754ea5ba26cdb28d56c77502c82700abbc9f20179b280187a64395073838ab19a4468fbe34994fabf89c45350000000000000000000000000000000000000000000000000000000000000000848cca5bffff0720080000000000000000000000000000000000000000000000000000000000000024076694d5e462e1696020c189cb523cf8d7d0119845bd4307be879b13540d49f41c925ff4",
+        "040000008d3b05d63b7d1cb9ddd7edc2680b8a0d203fdae22ea8135998f9e8f7015379063d29b2c19778880e3a8fdd3891ffceed31ebb9372e89a8e0c835861a687a36e60000000000000000000000000000000000000000000000000000000000000000c08cca5bffff072016000000000000000000000000000000000000000000000000000000000000002401f86658f21e7ed7d11a174b3344c5b1e9fa03d64a3494da855b995d4cd89786b5831b9e",
+        "0400000098873cac0bd64e0eafcf68e94e368846cf98dd3435eb241dc52a956bec5133056132b498ba94db20e66c35e4b1f279b34a79eb22cf1f851d66d15010b7e930f10000000000000000000000000000000000000000000000000000000000000000fc8cca5bffff07202f00000000000000000000000000000000000000000000000000000000000000240089cbff226675e529011c45c800ff95856123335b7b64db49e95f2844996d72d1b6a3c8",
+        "040000007970c3cccd28fe112c04d293acddd3aa1cadd474d041aa9b319d07cc7b61fe01e6d2558ba73ee1a00ce97a46d3b093868bc1a8b875ddddbc2bfed1348d7763c90000000000000000000000000000000000000000000000000000000000000000388dca5b51b806201b000000000000000000000000000000000000000000000000000000000000002406efc81d60cf2154d2161587f79755e2a965120c4739f7bce379dc16d48e2cf31e51dcf2",
+        "04000000833ad88a0673707965546b3786329399b6ac8daa53d4c8ab3609117b8272c0017a4830b04d60fb4ef642b9f3154a6acd81ca31902ffc49f4e17c873b9cc4da210000000000000000000000000000000000000000000000000000000000000000748dca5b20a806200a00000000000000000000000000000000000000000000000000000000000000240a472e9c510b2847471c2d899a383de745c70f60e3b8521cb67fd8116ac839f47a897317",
+        "040000002dec25b78ab4fac3a38439aa7dbf32e2e46b532f4261f03688477989a744ab03009eb791443f707605fdb0a7bf5a1745ef9adf18741628b2ee8291072138c4a00000000000000000000000000000000000000000000000000000000000000000b08dca5b22970620260000000000000000000000000000000000000000000000000000000000000024055d12fc818ebe41f123b8185cc3bc1a036e0e999e502301f2db8e187465f952cf62e192",
+        "040000002acbf72875e48922517437165f0be7fd5a905ed13bcd33f4beb51bab9f50c603925861ae494d3f5c1295f8efe5c40065023b84639b3321f10484fff3874c20490000000000000000000000000000000000000000000000000000000000000000ec8dca5b4d85062048000000000000000000000000000000000000000000000000000000000000002406664ad6b19571819807406098627704dde2134245e93cb74341ee42acb97fd57532bb7b",
+        "040000008c3a74015345e4d5f7225818490114d5547f9dd5b085eb7d8e57447ba825500638e032e30ff1c4002ba74d769e74e482c4cf88111f059031b292be6b57ff536f0000000000000000000000000000000000000000000000000000000000000000288eca5b9772062014000000000000000000000000000000000000000000000000000000000000002406be8cf91352ad8fb20da2d8dc15fc32e9ba1ac8ca9a751cab67e7373d6053d5b67b0192",
+        "04000000f528c7d3188dda1bad12d17abad8a3ac5c3c8a6891b6ee11172616c464b0ea01745f2de7c1d7b40e8da5e52e22d5f836c031c4c956a4fb2d8835d2edb74ec3b30000000000000000000000000000000000000000000000000000000000000000648eca5bf45e0620200000000000000000000000000000000000000000000000000000000000000024097c5d7e1173c87b5317d64bb0637b67cde70dbf5930d8171eebb414eb69d991ba6d1787",
+        "040000001c59fc473d593c73ecdca9e9caf57cb011cff13d218c04a03a9fcfdaec3d1f05a904c2ccfbc1a2916a5103c02b12a2505e3bd8b6414a2246793d45d6f9b8f6ee0000000000000000000000000000000000000000000000000000000000000000a08eca5b594a06202b000000000000000000000000000000000000000000000000000000000000002404076677f34ac5839737e40e32741b25756e0a8a06f0e30579277a163a961bb957bb01c8",
+        "040000008a505ef71a542b9b2da761bedf5cd41db256aed62e77d8b3afc22f09c6815d0009f57eb27e381add58e6e47c04530b46fa9b1992d2a11a12f04d8a2cef5a7a3f0000000000000000000000000000000000000000000000000000000000000000dc8eca5bb93406200d000000000000000000000000000000000000000000000000000000000000002403113c7fe34cf589350835cacf749ae9cda10599ce568130d57dc331255c93346e11477f",
+        "04000000afe74cbe11b2007ea4acc87f39b7e4b8086d5dbe28a52848b49ae4370a34530219047c6c594b1e24ab9fca2a1d3ac0c92c3093c529d2041f5fb2b6d5277b33fb0000000000000000000000000000000000000000000000000000000000000000188fca5b122606201a0000000000000000000000000000000000000000000000000000000000000024072b64d5422ca5ab7f0dba84582233f255dd1f204ba8e3a7e5bf392d4257b4d436aa89a1",
+        "0400000078c283eee8c612f1a7a6e087a7a06779d36cc7e948f89752be6d4c2e793222035945496904beb9e856f5fea4836a2770726a3b3e2225fdaa7fbc7265f9f218a20000000000000000000000000000000000000000000000000000000000000000548fca5b880e062002000000000000000000000000000000000000000000000000000000000000002403ab2f38e5e372452e1a245e7f046f6d4b3004a60c7f513c3921d927df13713435495acb",
+        "040000009ba424b1e9ba7c3495d3d83dfae3011c688851a5fd179dad37988f22e9985b04793b46b09b8096a743fe07c42f017ad82f50e448bb23d83274a1be45afe8f3960000000000000000000000000000000000000000000000000000000000000000908fca5b750006202f0000000000000000000000000000000000000000000000000000000000000024020bd81452fc36e9cd15e4910f8403d961c431604cbf74e3ad82f233b71536565f32d986",
+        "040000009353508dc9cade91ddee3a71f52eebf48b481d8202b0fa62ea79890c230a45021bfa22afbd24f154a0a0eec2139a910c9378d9eabca3aaa85e296e41851ba83e0000000000000000000000000000000000000000000000000000000000000000cc8fca5bdfe6052057000000000000000000000000000000000000000000000000000000000000002402569f73c3f2db65d20c891fd70377365d37047992f6d59d9197450abf665a429f0f51ab",
+        "0400000058b1628f5f2512bf1707c2ba03f9d3e8095599af009c26b1dfcbf74687c14100cb20eef3ad71a8a954ba5b69dc60eaebc80606b3711cde3024e117f595f2108100000000000000000000000000000000000000000000000000000000000000003090ca5b03cc05200c0000000000000000000000000000000000000000000000000000000000000024110d5999252b6dfb8b2170926e144a76a5cd143a065e850a8afffa31e595b403731579ae",
+        "0400000091fbbf70f8d7f9328eafbc87cd671729786fd1257b24a7529184d08701f2c1026d8b45ca5ee0dbbb58257cb536a5b277ca65de330a3be045d046d7feec73f55400000000000000000000000000000000000000000000000000000000000000009490ca5bceaf05201700000000000000000000000000000000000000000000000000000000000000240a32d874452bb1ffdb11749110876eca6fd80d74e9f524cb418fef25179cd7985f9ab369",
+        "04000000718b1b2d9dd12e78c9fc54e83a7fa1980564b6b82a997bb5fd27414898998f027d8b718fc0f2ca3e90a9c9dc6704b13beb5020d16752e3b395b783121428109e0000000000000000000000000000000000000000000000000000000000000000f890ca5b3092052091000000000000000000000000000000000000000000000000000000000000002403449618647725cd7b19555a5b01f6d1d3e60a18ee3be92e3fb7e90e331fd5b18170709a",
+        "0400000081192f7a517ffc91687ebe7bfc3ca21e9ab440a2080dcf501e4323ff5e263302516dbe907e268749a87a7d31b564eefebf96c517d45a289a94c26ba026e8bd8900000000000000000000000000000000000000000000000000000000000000005c91ca5b177305208a0000000000000000000000000000000000000000000000000000000000000024026a90d3e3de6187a70695c90e8316aceddd17f05e5621da28b4a1427e213dc54406dfec",
+        "0400000059d04fcc99342b8fda4309fdc3ba9120a85de19f8f64b8a8c534ed416b0ce40228db06f11bc6713165ca7032951ea30e6bf0b31b216815ab4231ef47299db71c0000000000000000000000000000000000000000000000000000000000000000c091ca5b705205204d0000000000000000000000000000000000000000000000000000000000000024012814b5408d88d913174d112a75dfb617b026a00d14e41715d6fc62e45bcff656c63751",
+        "0400000053a93759a4720fc72882d394e7389fd3aea3599936e7ea6a7e4bd8ad05f34501d618939fdcc91334d34120e688949dd7bd4c89a0b45ca8e0126ecb3822875d2000000000000000000000000000000000000000000000000000000000000000002492ca5b273005202000000000000000000000000000000000000000000000000000000000000000240160519bc3fde2716e07d8e796034fada2fe10552a192149b564f6345f11ebd6ec970da3",
+    ];
+
+    // Heights 31 to 37, forking from MAIN_CHAIN after height 30.
+    const FORK: &[&str] = &[
+        "0400000091fbbf70f8d7f9328eafbc87cd671729786fd1257b24a7529184d08701f2c102fcff1db3af4138e51b30ff38b5eed0fb132151dde240db589b0f19eff59fc83f00000000000000000000000000000000000000000000000000000000000000009490ca5bceaf0520c6000000000000000000000000000000000000000000000000000000000000002403fb22d3e1af85c3b34a531537569caae78c1e6bdf5622898e37f830609d1f33654f75f4",
+        "0400000004d59e31d94719ac387e8e4f5fc955401c9e599f43c3c6e5daa737f51db68103b583f4af867f88a9015a5c7ef3955ed1ecc488e604c923f6f535f3d41374df260000000000000000000000000000000000000000000000000000000000000000f890ca5b309205202800000000000000000000000000000000000000000000000000000000000000240b1c973e6224f96dc71152ea7ae3dc0101581e7a53fb73fc32b39c2151d48fb29f661d80",
+        "0400000096e348545deb7b63ad91edca0dd374351ce76cc51abe8d056049ed65f8c9760588d5fbc5b348b5241f7d15b155ee98038a324251fbafff36a3cc601f936a4a3300000000000000000000000000000000000000000000000000000000000000005c91ca5b1773052036000000000000000000000000000000000000000000000000000000000000002404e7197c018f3a5d9841d69114eb25c32fa30e439d6f112f5a6bc37c74a87ef96e82d188",
+        "0400000040a397cb11257c40bff6d645e0624119a4efeb81533b08dfed232c1f10d9010228ef00b833d73ff3529bf641699a25e304e6363af2b65ca986800bfc8566e71d0000000000000000000000000000000000000000000000000000000000000000c091ca5b705205200d000000000000000000000000000000000000000000000000000000000000002401fbed97e2f40931401055a4b6b5efa5f7da05310f37d1ba38af7607b3cf56f8275b89e0",
+        "04000000d1ca4a7b2a38e1a3ba0e43f36c051d7d1e557bc2560ee0139e3d2ed4d4554d032337ee9e8a8400771eca2337513a619da196abcf60f256a9aee19162e284bc4000000000000000000000000000000000000000000000000000000000000000002492ca5b27300520190000000000000000000000000000000000000000000000000000000000000024015a2a7f507d4667a3113d92f7a179397bd531d6dcffe46dbe8d7f464826bdd48d35bfc0",
+        "04000000014d2faf118de1f76f194c362de337f0efdd07f437b4cea715a198df9dc67b0158d74cab09ed0567367c1fcc76fdae2f280b51c3b8531c5227b3bbc2b597760a00000000000000000000000000000000000000000000000000000000000000008892ca5b932205201d0000000000000000000000000000000000000000000000000000000000000024047fd04e6386a605831973a51d646dcdc9e30a17a0d2510cb8786313d5d2f746c52a2b9b",
+        "04000000fa0f4dc55da11c0c2017642668d4e8a3b9999862ddb4937238e99d1a12ca31018757d3ba856db1f1eef2090d1ffb2254e887d50444b520542a7d5d05372a74ec0000000000000000000000000000000000000000000000000000000000000000ec92ca5bf314052003000000000000000000000000000000000000000000000000000000000000002400ce1a5cf7ad9313ff1c53aabe02b21717980f65855222c33d96fc267c8baa67363339b5",
+    ];
+
+    // Height 34, forking from MAIN_CHAIN after height 33.
+    const STALE: &[&str] = &[
+        "0400000059d04fcc99342b8fda4309fdc3ba9120a85de19f8f64b8a8c534ed416b0ce40253486563e7f1db5db29cda549619aad9751a2c524b275731d9fb543b9b1ff2c50000000000000000000000000000000000000000000000000000000000000000c091ca5b705205202200000000000000000000000000000000000000000000000000000000000000240017dc9115a30df3f9098c94ff14348d6d5b21a1f21c730b58d0a426d4a1f637de324b75",
+    ];
+
+    fn test_params() -> ChainParams {
+        ChainParams {
+            equihash_n: 48,
+            equihash_k: 5,
+            pow_limit: !U256::zero() >> 5,
+            averaging_window: 17,
+            max_adjust_down: 32,
+            max_adjust_up: 16,
+            target_spacing: 150,
+            min_difficulty_after: None,
+        }
+    }
+
+    fn headers(encoded: &[&str]) -> Vec<BlockHeader> {
+        encoded
+            .iter()
+            .map(|hex| {
+                let bytes: Vec<u8> = (0..hex.len() / 2)
+                    .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+                    .collect();
+                BlockHeader::read(&bytes[..]).unwrap()
+            })
+            .collect()
+    }
+
+    fn genesis_chain() -> HeaderChain {
+        let mut main = headers(MAIN_CHAIN);
+        main.truncate(1);
+        HeaderChain::new(test_params(), 0, main).unwrap()
+    }
+
+    fn modified<F: FnOnce(&mut BlockHeaderData)>(header: &BlockHeader, f: F) -> BlockHeader {
+        let mut data: BlockHeaderData = (**header).clone();
+        f(&mut data);
+        data.freeze().unwrap()
+    }
+
+    #[test]
+    fn extend_from_genesis() {
+        let main = headers(MAIN_CHAIN);
+        let mut chain = genesis_chain();
+
+        // Connect one header at a time, then the rest in one go
+        assert_eq!(
+            chain.connect(main[1..2].to_vec()),
+            Ok(ChainUpdate::Extended)
+        );
+        assert_eq!(chain.connect(main[2..].to_vec()), Ok(ChainUpdate::Extended));
+
+        assert_eq!(chain.tip_height(), 35);
+        assert_eq!(
+            chain.tip().hash().to_string(),
+            "011a96bc4f70e50045d22a44c5123a66dfd1be8d41111330fa92f5b753c5c7a2"
+        );
+        assert_eq!(chain.height_of(&main[20].hash()), Some(20));
+        assert_eq!(chain.get(20).unwrap().hash(), main[20].hash());
+        assert_eq!(
+            U256::from_le_bytes(&chain.chain_work()),
+            U256::from_u64(1334)
+        );
+
+        // The difficulty adjusted once the averaging window was full
+        assert_eq!(main[17].bits, 0x2007_ffff);
+        assert_ne!(main[18].bits, 0x2007_ffff);
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let main = headers(MAIN_CHAIN);
+        let mut chain = genesis_chain();
+        chain.connect(main[1..20].to_vec()).unwrap();
+
+        assert_eq!(
+            chain.connect(main[21..22].to_vec()),
+            Err(ChainError::UnknownParent(main[20].hash()))
+        );
+        assert_eq!(chain.connect(vec![]), Err(ChainError::Empty));
+
+        let mut skipping = main[20..23].to_vec();
+        skipping.remove(1);
+        assert_eq!(chain.connect(skipping), Err(ChainError::InvalidParent(21)));
+
+        let bad_solution = modified(&main[20], |h| h.nonce[0] ^= 1);
+        assert_eq!(
+            chain.connect(vec![bad_solution]),
+            Err(ChainError::InvalidSolution(20))
+        );
+
+        // Changing the target invalidates the solution, so these can't be
+        // checked without mining new headers. Check the expected target
+        // directly instead.
+        let candidate = super::Candidate {
+            start_height: 0,
+            headers: main[..20].iter().collect(),
+        };
+        assert_eq!(chain.check_header(&candidate, &main[20], 20), Ok(()));
+        assert_eq!(
+            chain.next_work_required(&candidate, &main[20], 20),
+            Ok(main[20].bits)
+        );
+        assert_eq!(
+            chain.check_header(&candidate, &main[21], 20),
+            Err(ChainError::UnexpectedTarget {
+                height: 20,
+                expected: main[20].bits,
+                actual: main[21].bits,
+            })
+        );
+
+        // Nothing was connected
+        assert_eq!(chain.tip_height(), 19);
+    }
+
+    #[test]
+    fn checkpoint_history() {
+        let main = headers(MAIN_CHAIN);
+
+        // 28 headers are enough to check the next one
+        let mut chain = HeaderChain::new(test_params(), 2, main[2..30].to_vec()).unwrap();
+        assert_eq!(
+            chain.connect(main[30..].to_vec()),
+            Ok(ChainUpdate::Extended)
+        );
+
+        // 27 are not
+        let mut chain = HeaderChain::new(test_params(), 3, main[3..30].to_vec()).unwrap();
+        assert_eq!(
+            chain.connect(main[30..].to_vec()),
+            Err(ChainError::InsufficientHistory(30))
+        );
+
+        // Checkpoint headers must be linked
+        let mut unlinked = main[..3].to_vec();
+        unlinked.remove(1);
+        assert_eq!(
+            HeaderChain::new(test_params(), 0, unlinked).err(),
+            Some(ChainError::InvalidParent(1))
+        );
+
+        // The checkpoint can't be reorganized
+        let mut chain = HeaderChain::new(test_params(), 0, main[..32].to_vec()).unwrap();
+        assert_eq!(
+            chain.connect(headers(FORK)),
+            Err(ChainError::ForkBeforeCheckpoint(30))
+        );
+    }
+
+    #[test]
+    fn reorganization() {
+        let main = headers(MAIN_CHAIN);
+        let fork = headers(FORK);
+        let mut chain = genesis_chain();
+        chain.connect(main[1..].to_vec()).unwrap();
+
+        // A branch with less work is not kept
+        assert_eq!(chain.connect(headers(STALE)), Ok(ChainUpdate::NotBest));
+        assert_eq!(chain.tip().hash(), main[35].hash());
+
+        // A branch with more work replaces the best chain above the fork
+        assert_eq!(chain.connect(fork[..2].to_vec()), Ok(ChainUpdate::NotBest));
+        assert_eq!(
+            chain.connect(fork.clone()),
+            Ok(ChainUpdate::Reorganized {
+                fork_height: 30,
+                disconnected: main[31..].iter().map(|h| h.hash()).collect(),
+            })
+        );
+        assert_eq!(chain.tip_height(), 37);
+        assert_eq!(chain.tip().hash(), fork[6].hash());
+        assert_eq!(chain.height_of(&main[31].hash()), None);
+        assert_eq!(
+            U256::from_le_bytes(&chain.chain_work()),
+            U256::from_u64(1433)
+        );
+
+        // The old branch is no longer part of the chain
+        assert_eq!(
+            chain.connect(main[32..].to_vec()),
+            Err(ChainError::UnknownParent(main[31].hash()))
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Consensus parameters: network upgrades and their consensus branch IDs.
+
+use std::fmt;
+
+/// A Zcash network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Network {
+    MainNetwork,
+    TestNetwork,
+}
+
+/// An event that occurs at a specified height on the Zcash chain, at which point the
+/// consensus rules enforced by the network are altered.
+///
+/// See [ZIP 200](https://zips.z.cash/zip-0200) for more details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkUpgrade {
+    /// The [Overwinter] network upgrade.
+    ///
+    /// [Overwinter]: https://z.cash/upgrade/overwinter/
+    Overwinter,
+    /// The [Sapling] network upgrade.
+    ///
+    /// [Sapling]: https://z.cash/upgrade/sapling/
+    Sapling,
+}
+
+/// The network upgrades on the Zcash chain in order of activation.
+///
+/// This order corresponds to the activation heights, but because Rust enums are
+/// full-fledged algebraic data types, we need to define it manually.
+const UPGRADES_IN_ORDER: &[NetworkUpgrade] = &[NetworkUpgrade::Overwinter, NetworkUpgrade::Sapling];
+
+impl NetworkUpgrade {
+    /// Returns the height at which this upgrade activates on the given network.
+    pub fn activation_height(&self, network: Network) -> u32 {
+        match (network, *self) {
+            (Network::MainNetwork, NetworkUpgrade::Overwinter) => 347_500,
+            (Network::MainNetwork, NetworkUpgrade::Sapling) => 419_200,
+            (Network::TestNetwork, NetworkUpgrade::Overwinter) => 207_500,
+            (Network::TestNetwork, NetworkUpgrade::Sapling) => 280_000,
+        }
+    }
+
+    /// Returns true if this upgrade is active at the given height on the given network.
+    pub fn is_active(&self, network: Network, height: u32) -> bool {
+        height >= self.activation_height(network)
+    }
+
+    fn branch_id(&self) -> BranchId {
+        match *self {
+            NetworkUpgrade::Overwinter => BranchId::Overwinter,
+            NetworkUpgrade::Sapling => BranchId::Sapling,
+        }
+    }
+}
+
+impl fmt::Display for NetworkUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NetworkUpgrade::Overwinter => write!(f, "Overwinter"),
+            NetworkUpgrade::Sapling => write!(f, "Sapling"),
+        }
+    }
+}
+
+/// A globally-unique identifier for a set of consensus rules within the Zcash chain.
+///
+/// Each branch ID in this enum corresponds to one of the epochs between a pair of Zcash
+/// network upgrades. For example, `BranchId::Overwinter` corresponds to the blocks
+/// starting at Overwinter activation, and ending the block before Sapling activation.
+///
+/// The main use of the branch ID is in signature generation: transactions commit to a
+/// specific branch ID by including it as part of [`signature_hash`]. This ensures
+/// two-way replay protection for transactions across network upgrades.
+///
+/// See [ZIP 200](https://zips.z.cash/zip-0200) for more details.
+///
+/// [`signature_hash`]: https://zips.z.cash/zip-0243
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchId {
+    /// The consensus rules at the launch of Zcash.
+    Sprout,
+    /// The consensus rules deployed by [`NetworkUpgrade::Overwinter`].
+    Overwinter,
+    /// The consensus rules deployed by [`NetworkUpgrade::Sapling`].
+    Sapling,
+}
+
+impl BranchId {
+    /// Returns the branch ID corresponding to the consensus rule set that is active at
+    /// the given height on the given network.
+    ///
+    /// This is the branch ID that should be used when creating transactions.
+    pub fn for_height(network: Network, height: u32) -> Self {
+        for nu in UPGRADES_IN_ORDER.iter().rev() {
+            if nu.is_active(network, height) {
+                return nu.branch_id();
+            }
+        }
+
+        // Sprout rules apply before any network upgrade
+        BranchId::Sprout
+    }
+
+    /// Parses a consensus branch ID, returning an error if it is unknown.
+    pub fn from_u32(value: u32) -> Result<Self, &'static str> {
+        match value {
+            0 => Ok(BranchId::Sprout),
+            0x5ba8_1b19 => Ok(BranchId::Overwinter),
+            0x76b8_09bb => Ok(BranchId::Sapling),
+            _ => Err("Unknown consensus branch ID"),
+        }
+    }
+}
+
+impl From<BranchId> for u32 {
+    fn from(consensus_branch_id: BranchId) -> u32 {
+        match consensus_branch_id {
+            BranchId::Sprout => 0,
+            BranchId::Overwinter => 0x5ba8_1b19,
+            BranchId::Sapling => 0x76b8_09bb,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BranchId, Network, NetworkUpgrade, UPGRADES_IN_ORDER};
+
+    #[test]
+    fn nu_ordering() {
+        for network in &[Network::MainNetwork, Network::TestNetwork] {
+            for i in 1..UPGRADES_IN_ORDER.len() {
+                let nu_a = UPGRADES_IN_ORDER[i - 1];
+                let nu_b = UPGRADES_IN_ORDER[i];
+                assert!(nu_a.activation_height(*network) < nu_b.activation_height(*network));
+            }
+        }
+    }
+
+    #[test]
+    fn nu_is_active() {
+        let network = Network::MainNetwork;
+        assert!(!NetworkUpgrade::Overwinter.is_active(network, 0));
+        assert!(!NetworkUpgrade::Overwinter.is_active(network, 347_499));
+        assert!(NetworkUpgrade::Overwinter.is_active(network, 347_500));
+        assert!(!NetworkUpgrade::Sapling.is_active(network, 419_199));
+        assert!(NetworkUpgrade::Sapling.is_active(network, 419_200));
+    }
+
+    #[test]
+    fn branch_id_from_u32() {
+        for &branch_id in &[BranchId::Sprout, BranchId::Overwinter, BranchId::Sapling] {
+            assert_eq!(BranchId::from_u32(u32::from(branch_id)), Ok(branch_id));
+        }
+        assert!(BranchId::from_u32(1).is_err());
+    }
+
+    #[test]
+    fn branch_id_for_height() {
+        let network = Network::MainNetwork;
+        assert_eq!(BranchId::for_height(network, 0), BranchId::Sprout);
+        assert_eq!(BranchId::for_height(network, 347_499), BranchId::Sprout);
+        assert_eq!(BranchId::for_height(network, 347_500), BranchId::Overwinter);
+        assert_eq!(BranchId::for_height(network, 419_199), BranchId::Overwinter);
+        assert_eq!(BranchId::for_height(network, 419_200), BranchId::Sapling);
+        assert_eq!(BranchId::for_height(network, 5_000_000), BranchId::Sapling);
+
+        let network = Network::TestNetwork;
+        assert_eq!(BranchId::for_height(network, 207_499), BranchId::Sprout);
+        assert_eq!(BranchId::for_height(network, 280_000), BranchId::Sapling);
+    }
+}
@@ -0,0 +1,481 @@
+//! Support for legacy transparent addresses and scripts.
+
+use ripemd160::{Digest as RipemdDigest, Ripemd160};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey, Signing};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::iter;
+use std::ops::Shl;
+
+use block::sha256d;
+use consensus::Network;
+use serialize::{read_compact, write_compact};
+
+/// The largest script we will read, in bytes. This is the consensus limit on
+/// script size in zcashd.
+const MAX_SCRIPT_SIZE: u64 = 10_000;
+
+/// The `SIGHASH_ALL` signature hash type, which commits to every input and
+/// output of the transaction.
+pub const SIGHASH_ALL: u8 = 0x01;
+
+const B58_PUBKEY_ADDRESS_PREFIX_MAINNET: [u8; 2] = [0x1c, 0xb8];
+const B58_SCRIPT_ADDRESS_PREFIX_MAINNET: [u8; 2] = [0x1c, 0xbd];
+const B58_PUBKEY_ADDRESS_PREFIX_TESTNET: [u8; 2] = [0x1d, 0x25];
+const B58_SCRIPT_ADDRESS_PREFIX_TESTNET: [u8; 2] = [0x1c, 0xba];
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Defined script opcodes.
+///
+/// Only the opcodes used by the standard P2PKH and P2SH scripts are listed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpCode {
+    // push value
+    PushData1 = 0x4c,
+    PushData2 = 0x4d,
+    PushData4 = 0x4e,
+
+    // stack ops
+    Dup = 0x76,
+
+    // bit logic
+    Equal = 0x87,
+    EqualVerify = 0x88,
+
+    // crypto
+    Hash160 = 0xa9,
+    CheckSig = 0xac,
+}
+
+/// A serialized script, used inside transparent inputs and outputs of a transaction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Script(pub Vec<u8>);
+
+impl Script {
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let len = read_compact(&mut reader)?;
+        if len > MAX_SCRIPT_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "script is too large",
+            ));
+        }
+        let mut script = vec![0; len as usize];
+        reader.read_exact(&mut script)?;
+        Ok(Script(script))
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_compact(&mut writer, self.0.len() as u64)?;
+        writer.write_all(&self.0)
+    }
+
+    /// Returns the address that this script pays to, if it is a standard
+    /// P2PKH or P2SH script.
+    pub fn address(&self) -> Option<TransparentAddress> {
+        let s = &self.0;
+        if s.len() == 25
+            && s[0] == OpCode::Dup as u8
+            && s[1] == OpCode::Hash160 as u8
+            && s[2] == 0x14
+            && s[23] == OpCode::EqualVerify as u8
+            && s[24] == OpCode::CheckSig as u8
+        {
+            let mut hash = [0; 20];
+            hash.copy_from_slice(&s[3..23]);
+            Some(TransparentAddress::PublicKey(hash))
+        } else if s.len() == 23
+            && s[0] == OpCode::Hash160 as u8
+            && s[1] == 0x14
+            && s[22] == OpCode::Equal as u8
+        {
+            let mut hash = [0; 20];
+            hash.copy_from_slice(&s[2..22]);
+            Some(TransparentAddress::Script(hash))
+        } else {
+            None
+        }
+    }
+}
+
+impl Shl<OpCode> for Script {
+    type Output = Self;
+
+    fn shl(mut self, rhs: OpCode) -> Self {
+        self.0.push(rhs as u8);
+        self
+    }
+}
+
+/// Appends a push of `data`, using the smallest push opcode that fits it.
+impl<'a> Shl<&'a [u8]> for Script {
+    type Output = Self;
+
+    fn shl(mut self, data: &'a [u8]) -> Self {
+        let len = data.len();
+        if len < OpCode::PushData1 as usize {
+            self.0.push(len as u8);
+        } else if len <= 0xff {
+            self.0.push(OpCode::PushData1 as u8);
+            self.0.push(len as u8);
+        } else if len <= 0xffff {
+            self.0.push(OpCode::PushData2 as u8);
+            self.0.extend_from_slice(&[len as u8, (len >> 8) as u8]);
+        } else {
+            self.0.push(OpCode::PushData4 as u8);
+            self.0.extend_from_slice(&[
+                len as u8,
+                (len >> 8) as u8,
+                (len >> 16) as u8,
+                (len >> 24) as u8,
+            ]);
+        }
+        self.0.extend_from_slice(data);
+        self
+    }
+}
+
+/// A transparent address corresponding to either a public key or a `Script`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransparentAddress {
+    PublicKey([u8; 20]),
+    Script([u8; 20]),
+}
+
+impl TransparentAddress {
+    /// Returns the P2PKH address for the compressed encoding of `pubkey`.
+    pub fn from_pubkey(pubkey: &PublicKey) -> Self {
+        TransparentAddress::PublicKey(hash160(&pubkey.serialize()))
+    }
+
+    /// Returns the P2SH address for the given redeem script.
+    pub fn from_script(redeem_script: &Script) -> Self {
+        TransparentAddress::Script(hash160(&redeem_script.0))
+    }
+
+    /// Generate the `scriptPubKey` corresponding to this address.
+    pub fn script(&self) -> Script {
+        match *self {
+            TransparentAddress::PublicKey(ref key_id) => {
+                // P2PKH script
+                Script::default()
+                    << OpCode::Dup
+                    << OpCode::Hash160
+                    << &key_id[..]
+                    << OpCode::EqualVerify
+                    << OpCode::CheckSig
+            }
+            TransparentAddress::Script(ref script_id) => {
+                // P2SH script
+                Script::default() << OpCode::Hash160 << &script_id[..] << OpCode::Equal
+            }
+        }
+    }
+
+    /// Encodes this address in Base58Check for the given network.
+    pub fn encode(&self, network: Network) -> String {
+        let (prefix, hash) = match (*self, network) {
+            (TransparentAddress::PublicKey(ref hash), Network::MainNetwork) => {
+                (B58_PUBKEY_ADDRESS_PREFIX_MAINNET, *hash)
+            }
+            (TransparentAddress::Script(ref hash), Network::MainNetwork) => {
+                (B58_SCRIPT_ADDRESS_PREFIX_MAINNET, *hash)
+            }
+            (TransparentAddress::PublicKey(ref hash), Network::TestNetwork) => {
+                (B58_PUBKEY_ADDRESS_PREFIX_TESTNET, *hash)
+            }
+            (TransparentAddress::Script(ref hash), Network::TestNetwork) => {
+                (B58_SCRIPT_ADDRESS_PREFIX_TESTNET, *hash)
+            }
+        };
+
+        let mut payload = prefix.to_vec();
+        payload.extend_from_slice(&hash);
+        base58check_encode(&payload)
+    }
+
+    /// Decodes a Base58Check-encoded address for the given network.
+    pub fn decode(network: Network, s: &str) -> Result<Self, ParseAddressError> {
+        let payload = base58check_decode(s)?;
+        if payload.len() != 22 {
+            return Err(ParseAddressError::InvalidLength);
+        }
+
+        let mut prefix = [0; 2];
+        prefix.copy_from_slice(&payload[..2]);
+        let mut hash = [0; 20];
+        hash.copy_from_slice(&payload[2..]);
+
+        match (prefix, network) {
+            (B58_PUBKEY_ADDRESS_PREFIX_MAINNET, Network::MainNetwork)
+            | (B58_PUBKEY_ADDRESS_PREFIX_TESTNET, Network::TestNetwork) => {
+                Ok(TransparentAddress::PublicKey(hash))
+            }
+            (B58_SCRIPT_ADDRESS_PREFIX_MAINNET, Network::MainNetwork)
+            | (B58_SCRIPT_ADDRESS_PREFIX_TESTNET, Network::TestNetwork) => {
+                Ok(TransparentAddress::Script(hash))
+            }
+            (B58_PUBKEY_ADDRESS_PREFIX_MAINNET, _)
+            | (B58_SCRIPT_ADDRESS_PREFIX_MAINNET, _)
+            | (B58_PUBKEY_ADDRESS_PREFIX_TESTNET, _)
+            | (B58_SCRIPT_ADDRESS_PREFIX_TESTNET, _) => Err(ParseAddressError::WrongNetwork),
+            _ => Err(ParseAddressError::UnknownPrefix),
+        }
+    }
+}
+
+/// Errors that can occur when decoding a Base58Check transparent address.
+#[derive(Debug, PartialEq)]
+pub enum ParseAddressError {
+    /// The string contained a character outside the Base58 alphabet.
+    InvalidCharacter,
+    /// The checksum did not match the payload.
+    InvalidChecksum,
+    /// The payload was not a two-byte prefix followed by a 20-byte hash.
+    InvalidLength,
+    /// The prefix is not that of a transparent address.
+    UnknownPrefix,
+    /// The address is for a different network.
+    WrongNetwork,
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseAddressError::InvalidCharacter => write!(f, "invalid Base58 character"),
+            ParseAddressError::InvalidChecksum => write!(f, "invalid Base58Check checksum"),
+            ParseAddressError::InvalidLength => write!(f, "invalid address length"),
+            ParseAddressError::UnknownPrefix => write!(f, "not a transparent address"),
+            ParseAddressError::WrongNetwork => write!(f, "address is for a different network"),
+        }
+    }
+}
+
+/// Signs a transparent input that spends a P2PKH output, and returns its
+/// `scriptSig`.
+///
+/// `sighash` is the input's signature hash (as specified in ZIP 243),
+/// computed with the given `hash_type`.
+pub fn sign_p2pkh<C: Signing>(
+    secp: &Secp256k1<C>,
+    sk: &SecretKey,
+    sighash: &[u8; 32],
+    hash_type: u8,
+) -> Script {
+    let msg = Message::from_slice(sighash).expect("32 bytes");
+    let mut sig = secp.sign(&msg, sk).serialize_der().to_vec();
+    sig.push(hash_type);
+
+    let pubkey = PublicKey::from_secret_key(secp, sk);
+    Script::default() << &sig[..] << &pubkey.serialize()[..]
+}
+
+/// RIPEMD-160(SHA-256(data)), used to derive transparent addresses.
+fn hash160(data: &[u8]) -> [u8; 20] {
+    let mut result = [0; 20];
+    result.copy_from_slice(&Ripemd160::digest(&Sha256::digest(data)));
+    result
+}
+
+fn base58check_encode(payload: &[u8]) -> String {
+    let mut data = payload.to_vec();
+    data.extend_from_slice(&sha256d(payload)[..4]);
+
+    // Each leading zero byte is encoded as a '1'
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+
+    // Little-endian base-58 digits of the remaining bytes
+    let mut digits: Vec<u8> = vec![];
+    for byte in &data[zeros..] {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    iter::repeat(b'1')
+        .take(zeros)
+        .chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize]))
+        .map(char::from)
+        .collect()
+}
+
+fn base58check_decode(s: &str) -> Result<Vec<u8>, ParseAddressError> {
+    let zeros = s.bytes().take_while(|c| *c == b'1').count();
+
+    // Little-endian bytes of the value after the leading '1's
+    let mut bytes: Vec<u8> = vec![];
+    for c in s.bytes().skip(zeros) {
+        let mut carry = match BASE58_ALPHABET.iter().position(|a| *a == c) {
+            Some(digit) => digit as u32,
+            None => return Err(ParseAddressError::InvalidCharacter),
+        };
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut data: Vec<u8> = iter::repeat(0)
+        .take(zeros)
+        .chain(bytes.into_iter().rev())
+        .collect();
+    if data.len() < 4 {
+        return Err(ParseAddressError::InvalidChecksum);
+    }
+
+    let checksum = data.split_off(data.len() - 4);
+    if checksum[..] != sha256d(&data)[..4] {
+        return Err(ParseAddressError::InvalidChecksum);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::{Message, PublicKey, Secp256k1, SecretKey, Signature};
+
+    use super::{
+        base58check_decode, base58check_encode, sign_p2pkh, OpCode, ParseAddressError, Script,
+        TransparentAddress, SIGHASH_ALL,
+    };
+    use consensus::Network;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len() / 2)
+            .map(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn base58check() {
+        // The P2PKH example from the Bitcoin wiki, with version byte 0x00
+        let pubkey = PublicKey::from_slice(&from_hex(
+            "0250863ad64a87ae8a2fe83c1af1a8403cb53f53e486d8511dad8a04887e5b2352",
+        ))
+        .unwrap();
+        let hash = match TransparentAddress::from_pubkey(&pubkey) {
+            TransparentAddress::PublicKey(hash) => hash,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            hash.to_vec(),
+            from_hex("f54a5851e9372b87810a8e60cdd2e7cfd80b6e31")
+        );
+
+        let mut payload = vec![0x00];
+        payload.extend_from_slice(&hash);
+        let encoded = base58check_encode(&payload);
+        assert_eq!(encoded, "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+        assert_eq!(base58check_decode(&encoded), Ok(payload));
+
+        assert_eq!(
+            base58check_decode("1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt"),
+            Err(ParseAddressError::InvalidChecksum)
+        );
+        assert_eq!(
+            base58check_decode("1PMycacnJaSqwwJqjawXBErnLsZ7RkXUA0"),
+            Err(ParseAddressError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn address_encoding() {
+        let hash = [7; 20];
+        for &(addr, network, prefix) in &[
+            (
+                TransparentAddress::PublicKey(hash),
+                Network::MainNetwork,
+                "t1",
+            ),
+            (TransparentAddress::Script(hash), Network::MainNetwork, "t3"),
+            (
+                TransparentAddress::PublicKey(hash),
+                Network::TestNetwork,
+                "tm",
+            ),
+            (TransparentAddress::Script(hash), Network::TestNetwork, "t2"),
+        ] {
+            let encoded = addr.encode(network);
+            assert!(encoded.starts_with(prefix));
+            assert_eq!(encoded.len(), 35);
+            assert_eq!(TransparentAddress::decode(network, &encoded), Ok(addr));
+        }
+
+        let mainnet = TransparentAddress::PublicKey(hash).encode(Network::MainNetwork);
+        assert_eq!(
+            TransparentAddress::decode(Network::TestNetwork, &mainnet),
+            Err(ParseAddressError::WrongNetwork)
+        );
+        assert_eq!(
+            TransparentAddress::decode(Network::MainNetwork, "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs"),
+            Err(ParseAddressError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn script_round_trip() {
+        let hash = [7; 20];
+        for addr in &[
+            TransparentAddress::PublicKey(hash),
+            TransparentAddress::Script(hash),
+        ] {
+            let script = addr.script();
+            assert_eq!(script.address(), Some(*addr));
+
+            let mut encoded = vec![];
+            script.write(&mut encoded).unwrap();
+            assert_eq!(encoded[0] as usize, script.0.len());
+            assert_eq!(Script::read(&encoded[..]).unwrap(), script);
+        }
+
+        assert_eq!(
+            TransparentAddress::PublicKey(hash).script().0[..3],
+            [0x76, 0xa9, 0x14]
+        );
+        assert_eq!(Script(vec![0x76, 0xa9]).address(), None);
+
+        // Large pushes use the PUSHDATA opcodes
+        let data = [0; 0x100];
+        let script = Script::default() << &data[..0x4b];
+        assert_eq!(script.0[0], 0x4b);
+        let script = Script::default() << &data[..0x4c];
+        assert_eq!(script.0[..2], [OpCode::PushData1 as u8, 0x4c]);
+        let script = Script::default() << &data[..];
+        assert_eq!(script.0[..3], [OpCode::PushData2 as u8, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn p2pkh_signature() {
+        let secp = Secp256k1::new();
+        let sk = SecretKey::from_slice(&[1; 32]).unwrap();
+        let pubkey = PublicKey::from_secret_key(&secp, &sk);
+        let sighash = [42; 32];
+
+        let script_sig = sign_p2pkh(&secp, &sk, &sighash, SIGHASH_ALL);
+
+        // <sig || hash_type> <pubkey>
+        let sig_len = script_sig.0[0] as usize;
+        let sig = &script_sig.0[1..sig_len + 1];
+        assert_eq!(sig[sig_len - 1], SIGHASH_ALL);
+        assert_eq!(script_sig.0[sig_len + 1], 33);
+        assert_eq!(&script_sig.0[sig_len + 2..], &pubkey.serialize()[..]);
+
+        let sig = Signature::from_der(&sig[..sig_len - 1]).unwrap();
+        let msg = Message::from_slice(&sighash).unwrap();
+        assert!(secp.verify(&msg, &sig, &pubkey).is_ok());
+    }
+}
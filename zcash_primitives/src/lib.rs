@@ -1,3 +1,19 @@
+extern crate blake2_rfc;
+extern crate byteorder;
+extern crate ripemd160;
+extern crate secp256k1;
+extern crate sha2;
+
+pub mod amount;
+pub mod block;
+pub mod chain;
+pub mod consensus;
+pub mod equihash;
+pub mod legacy;
+pub mod mmr;
+mod pow;
+mod serialize;
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,564 @@
+//! Implementation of a Merkle Mountain Range over block metadata, committing
+//! to the chain history in the style of FlyClient (see ZIP 221).
+
+use blake2_rfc::blake2b::Blake2b;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{self, Read, Write};
+
+use serialize::{read_compact, write_compact};
+
+/// Prefix of the BLAKE2b personalization for history tree nodes. The
+/// remaining four bytes are the little-endian consensus branch ID.
+pub const HISTORY_PERSONALIZATION_PREFIX: &'static [u8; 12] = b"ZcashHistory";
+
+/// The maximum serialized length of a node.
+pub const MAX_NODE_DATA_SIZE: usize = 32 // subtree commitment
+    + 4 // start time
+    + 4 // end time
+    + 4 // start target
+    + 4 // end target
+    + 32 // start sapling root
+    + 32 // end sapling root
+    + 32 // subtree total work
+    + 9 // start height (compact uint)
+    + 9 // end height (compact uint)
+    + 9; // shielded transaction count (compact uint)
+
+/// Metadata committed to by a node of the history tree.
+///
+/// A leaf describes a single block; an internal node summarises the range of
+/// blocks covered by its subtree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeData {
+    /// The block hash for a leaf, or the hash of both children for a node.
+    pub subtree_commitment: [u8; 32],
+    pub start_time: u32,
+    pub end_time: u32,
+    pub start_target: u32,
+    pub end_target: u32,
+    pub start_sapling_root: [u8; 32],
+    pub end_sapling_root: [u8; 32],
+    /// Cumulative work of the subtree, as a little-endian 256-bit integer.
+    pub subtree_total_work: [u8; 32],
+    pub start_height: u64,
+    pub end_height: u64,
+    /// Number of transactions with shielded components in the subtree.
+    pub shielded_tx: u64,
+}
+
+fn personalization(consensus_branch_id: u32) -> [u8; 16] {
+    let mut persona = [0; 16];
+    persona[..12].copy_from_slice(HISTORY_PERSONALIZATION_PREFIX);
+    (&mut persona[12..])
+        .write_u32::<LittleEndian>(consensus_branch_id)
+        .unwrap();
+    persona
+}
+
+fn add_work(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut result = [0; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let sum = a[i] as u16 + b[i] as u16 + carry;
+        result[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    result
+}
+
+impl NodeData {
+    /// Constructs the leaf describing a single block.
+    pub fn from_block(
+        block_hash: [u8; 32],
+        time: u32,
+        target: u32,
+        sapling_root: [u8; 32],
+        work: [u8; 32],
+        height: u64,
+        shielded_tx: u64,
+    ) -> Self {
+        NodeData {
+            subtree_commitment: block_hash,
+            start_time: time,
+            end_time: time,
+            start_target: target,
+            end_target: target,
+            start_sapling_root: sapling_root,
+            end_sapling_root: sapling_root,
+            subtree_total_work: work,
+            start_height: height,
+            end_height: height,
+            shielded_tx,
+        }
+    }
+
+    /// Computes the parent of two adjacent subtrees, where `left` covers the
+    /// earlier blocks.
+    ///
+    /// Returns `None` if the total shielded transaction count overflows,
+    /// which can only happen for nodes that were not built from real blocks.
+    pub fn combine(left: &NodeData, right: &NodeData, consensus_branch_id: u32) -> Option<Self> {
+        let shielded_tx = left.shielded_tx.checked_add(right.shielded_tx)?;
+
+        let mut h = Blake2b::with_params(32, &[], &[], &personalization(consensus_branch_id));
+        h.update(&left.to_bytes());
+        h.update(&right.to_bytes());
+        let mut subtree_commitment = [0; 32];
+        subtree_commitment.copy_from_slice(h.finalize().as_bytes());
+
+        Some(NodeData {
+            subtree_commitment,
+            start_time: left.start_time,
+            end_time: right.end_time,
+            start_target: left.start_target,
+            end_target: right.end_target,
+            start_sapling_root: left.start_sapling_root,
+            end_sapling_root: right.end_sapling_root,
+            subtree_total_work: add_work(&left.subtree_total_work, &right.subtree_total_work),
+            start_height: left.start_height,
+            end_height: right.end_height,
+            shielded_tx,
+        })
+    }
+
+    /// Computes the commitment to this node, as used for the history root.
+    pub fn hash(&self, consensus_branch_id: u32) -> [u8; 32] {
+        let mut h = Blake2b::with_params(32, &[], &[], &personalization(consensus_branch_id));
+        h.update(&self.to_bytes());
+        let mut result = [0; 32];
+        result.copy_from_slice(h.finalize().as_bytes());
+        result
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut subtree_commitment = [0; 32];
+        reader.read_exact(&mut subtree_commitment)?;
+        let start_time = reader.read_u32::<LittleEndian>()?;
+        let end_time = reader.read_u32::<LittleEndian>()?;
+        let start_target = reader.read_u32::<LittleEndian>()?;
+        let end_target = reader.read_u32::<LittleEndian>()?;
+        let mut start_sapling_root = [0; 32];
+        reader.read_exact(&mut start_sapling_root)?;
+        let mut end_sapling_root = [0; 32];
+        reader.read_exact(&mut end_sapling_root)?;
+        let mut subtree_total_work = [0; 32];
+        reader.read_exact(&mut subtree_total_work)?;
+        let start_height = read_compact(&mut reader)?;
+        let end_height = read_compact(&mut reader)?;
+        let shielded_tx = read_compact(&mut reader)?;
+
+        Ok(NodeData {
+            subtree_commitment,
+            start_time,
+            end_time,
+            start_target,
+            end_target,
+            start_sapling_root,
+            end_sapling_root,
+            subtree_total_work,
+            start_height,
+            end_height,
+            shielded_tx,
+        })
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.subtree_commitment)?;
+        writer.write_u32::<LittleEndian>(self.start_time)?;
+        writer.write_u32::<LittleEndian>(self.end_time)?;
+        writer.write_u32::<LittleEndian>(self.start_target)?;
+        writer.write_u32::<LittleEndian>(self.end_target)?;
+        writer.write_all(&self.start_sapling_root)?;
+        writer.write_all(&self.end_sapling_root)?;
+        writer.write_all(&self.subtree_total_work)?;
+        write_compact(&mut writer, self.start_height)?;
+        write_compact(&mut writer, self.end_height)?;
+        write_compact(&mut writer, self.shielded_tx)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(MAX_NODE_DATA_SIZE);
+        self.write(&mut result)
+            .expect("should be able to serialize NodeData");
+        result
+    }
+}
+
+#[derive(Clone)]
+struct Entry {
+    data: NodeData,
+    height: u32,
+    parent: Option<usize>,
+    children: Option<(usize, usize)>,
+}
+
+/// An append-only Merkle Mountain Range of block metadata.
+///
+/// Nodes are stored in insertion order; the tree is a list of perfect binary
+/// subtrees ("peaks") of strictly decreasing height, which are bagged from the
+/// right to produce the root.
+#[derive(Clone)]
+pub struct Tree {
+    consensus_branch_id: u32,
+    entries: Vec<Entry>,
+    leaves: Vec<usize>,
+    peaks: Vec<usize>,
+}
+
+/// A sibling along the path from a leaf to its peak.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sibling {
+    Left(NodeData),
+    Right(NodeData),
+}
+
+/// Evidence that a leaf is committed to by a given history root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InclusionProof {
+    /// Siblings from the leaf up to (but excluding) its peak.
+    pub path: Vec<Sibling>,
+    /// The other peaks of the tree, in order.
+    pub peaks: Vec<NodeData>,
+    /// Where the peak containing the leaf sits among `peaks`.
+    pub peak_position: usize,
+}
+
+fn bag_peaks(peaks: &[NodeData], consensus_branch_id: u32) -> Option<NodeData> {
+    let mut iter = peaks.iter().rev();
+    let mut root = match iter.next() {
+        Some(last) => last.clone(),
+        None => return None,
+    };
+    for peak in iter {
+        root = NodeData::combine(peak, &root, consensus_branch_id)?;
+    }
+    Some(root)
+}
+
+impl Tree {
+    /// Creates an empty tree whose nodes are hashed under the given
+    /// consensus branch ID.
+    pub fn new(consensus_branch_id: u32) -> Self {
+        Tree {
+            consensus_branch_id,
+            entries: vec![],
+            leaves: vec![],
+            peaks: vec![],
+        }
+    }
+
+    /// Returns the number of leaves (blocks) in the tree.
+    pub fn len(&self) -> u64 {
+        self.leaves.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Appends a leaf, merging peaks of equal height, and returns the index
+    /// of the new leaf.
+    ///
+    /// Returns `None`, leaving the tree unchanged, if the tree's total
+    /// shielded transaction count would overflow.
+    pub fn append(&mut self, leaf: NodeData) -> Option<u64> {
+        // The peaks cover every leaf, so if their total fits then so does
+        // every node merged below
+        self.peaks.iter().try_fold(leaf.shielded_tx, |total, &p| {
+            total.checked_add(self.entries[p].data.shielded_tx)
+        })?;
+
+        let leaf_index = self.leaves.len() as u64;
+
+        self.entries.push(Entry {
+            data: leaf,
+            height: 0,
+            parent: None,
+            children: None,
+        });
+        let pos = self.entries.len() - 1;
+        self.leaves.push(pos);
+        self.peaks.push(pos);
+
+        while self.peaks.len() >= 2 {
+            let right = self.peaks[self.peaks.len() - 1];
+            let left = self.peaks[self.peaks.len() - 2];
+            if self.entries[left].height != self.entries[right].height {
+                break;
+            }
+
+            let data = NodeData::combine(
+                &self.entries[left].data,
+                &self.entries[right].data,
+                self.consensus_branch_id,
+            )
+            .expect("the total shielded transaction count was checked");
+            let height = self.entries[left].height + 1;
+            self.entries.push(Entry {
+                data,
+                height,
+                parent: None,
+                children: Some((left, right)),
+            });
+            let parent = self.entries.len() - 1;
+            self.entries[left].parent = Some(parent);
+            self.entries[right].parent = Some(parent);
+
+            self.peaks.pop();
+            self.peaks.pop();
+            self.peaks.push(parent);
+        }
+
+        Some(leaf_index)
+    }
+
+    /// Returns the node covering the whole tree, or `None` if it is empty.
+    pub fn root(&self) -> Option<NodeData> {
+        // append checked that the peaks' total shielded transaction count
+        // fits, so bagging them cannot overflow
+        let peaks: Vec<_> = self
+            .peaks
+            .iter()
+            .map(|&p| self.entries[p].data.clone())
+            .collect();
+        bag_peaks(&peaks, self.consensus_branch_id)
+    }
+
+    /// Returns the history root commitment, or `None` if the tree is empty.
+    pub fn root_hash(&self) -> Option<[u8; 32]> {
+        self.root().map(|root| root.hash(self.consensus_branch_id))
+    }
+
+    /// Returns the leaf at the given index.
+    pub fn leaf(&self, leaf_index: u64) -> Option<&NodeData> {
+        self.leaves
+            .get(leaf_index as usize)
+            .map(|&pos| &self.entries[pos].data)
+    }
+
+    /// Constructs a proof that the given leaf is committed to by the current
+    /// root, or `None` if there is no such leaf.
+    pub fn inclusion_proof(&self, leaf_index: u64) -> Option<InclusionProof> {
+        let mut pos = match self.leaves.get(leaf_index as usize) {
+            Some(&pos) => pos,
+            None => return None,
+        };
+
+        let mut path = vec![];
+        while let Some(parent) = self.entries[pos].parent {
+            let (left, right) = self.entries[parent]
+                .children
+                .expect("parents have children");
+            if left == pos {
+                path.push(Sibling::Right(self.entries[right].data.clone()));
+            } else {
+                path.push(Sibling::Left(self.entries[left].data.clone()));
+            }
+            pos = parent;
+        }
+
+        let peak_position = self
+            .peaks
+            .iter()
+            .position(|&p| p == pos)
+            .expect("the topmost ancestor is a peak");
+        let peaks = self
+            .peaks
+            .iter()
+            .filter(|&&p| p != pos)
+            .map(|&p| self.entries[p].data.clone())
+            .collect();
+
+        Some(InclusionProof {
+            path,
+            peaks,
+            peak_position,
+        })
+    }
+}
+
+impl InclusionProof {
+    /// Checks that `leaf` is committed to by the history root `root_hash`.
+    pub fn verify(&self, leaf: &NodeData, root_hash: &[u8; 32], consensus_branch_id: u32) -> bool {
+        if self.peak_position > self.peaks.len() {
+            return false;
+        }
+
+        let mut node = leaf.clone();
+        for sibling in &self.path {
+            let parent = match *sibling {
+                Sibling::Left(ref left) => NodeData::combine(left, &node, consensus_branch_id),
+                Sibling::Right(ref right) => NodeData::combine(&node, right, consensus_branch_id),
+            };
+            node = match parent {
+                Some(parent) => parent,
+                None => return false,
+            };
+        }
+
+        let mut peaks = self.peaks.clone();
+        peaks.insert(self.peak_position, node);
+
+        match bag_peaks(&peaks, consensus_branch_id) {
+            Some(root) => &root.hash(consensus_branch_id) == root_hash,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BRANCH_ID: u32 = 0x76b809bb;
+
+    fn leaf(height: u64) -> NodeData {
+        let mut block_hash = [0; 32];
+        (&mut block_hash[..])
+            .write_u64::<LittleEndian>(height)
+            .unwrap();
+        let mut work = [0; 32];
+        work[0] = 1;
+        NodeData::from_block(
+            block_hash,
+            1_000_000 + height as u32,
+            0x1f07ffff,
+            [7; 32],
+            work,
+            height,
+            height % 3,
+        )
+    }
+
+    #[test]
+    fn node_data_round_trip() {
+        let mut node = leaf(1_000_000);
+        node.shielded_tx = 0x1_0000_0000;
+
+        let mut data = vec![];
+        node.write(&mut data).unwrap();
+        assert!(data.len() <= MAX_NODE_DATA_SIZE);
+        assert_eq!(NodeData::read(&data[..]).unwrap(), node);
+    }
+
+    #[test]
+    fn combine_summarises_range() {
+        let node = NodeData::combine(&leaf(10), &leaf(11), BRANCH_ID).unwrap();
+        assert_eq!(node.start_height, 10);
+        assert_eq!(node.end_height, 11);
+        assert_eq!(node.start_time, leaf(10).start_time);
+        assert_eq!(node.end_time, leaf(11).end_time);
+        assert_eq!(
+            node.shielded_tx,
+            leaf(10).shielded_tx + leaf(11).shielded_tx
+        );
+        assert_eq!(node.subtree_total_work[0], 2);
+    }
+
+    #[test]
+    fn combine_rejects_overflow() {
+        let mut a = leaf(10);
+        a.shielded_tx = u64::max_value() - 1;
+        let mut b = leaf(11);
+        b.shielded_tx = 2;
+        assert_eq!(NodeData::combine(&a, &b, BRANCH_ID), None);
+
+        b.shielded_tx = 1;
+        assert_eq!(
+            NodeData::combine(&a, &b, BRANCH_ID).unwrap().shielded_tx,
+            u64::max_value()
+        );
+
+        // The tree rejects a leaf that would overflow its total, and is
+        // unchanged
+        let mut tree = Tree::new(BRANCH_ID);
+        tree.append(a).unwrap();
+        tree.append(leaf(0)).unwrap();
+        let root_hash = tree.root_hash();
+        let mut c = leaf(12);
+        c.shielded_tx = u64::max_value();
+        assert_eq!(tree.append(c), None);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.root_hash(), root_hash);
+
+        // So does an inclusion proof whose sibling overflows
+        let mut proof = tree.inclusion_proof(1).unwrap();
+        proof.path[0] = Sibling::Left(b);
+        let mut huge = leaf(0);
+        huge.shielded_tx = u64::max_value();
+        assert!(!proof.verify(&huge, &root_hash.unwrap(), BRANCH_ID));
+    }
+
+    #[test]
+    fn work_carries() {
+        let mut a = [0; 32];
+        a[0] = 0xff;
+        a[1] = 0xff;
+        let mut b = [0; 32];
+        b[0] = 1;
+        let sum = add_work(&a, &b);
+        assert_eq!(&sum[..3], &[0, 0, 1]);
+    }
+
+    #[test]
+    fn append_and_root() {
+        let mut tree = Tree::new(BRANCH_ID);
+        assert!(tree.root_hash().is_none());
+
+        tree.append(leaf(0)).unwrap();
+        assert_eq!(tree.root(), Some(leaf(0)));
+
+        tree.append(leaf(1)).unwrap();
+        assert_eq!(
+            tree.root(),
+            NodeData::combine(&leaf(0), &leaf(1), BRANCH_ID)
+        );
+
+        tree.append(leaf(2)).unwrap();
+        assert_eq!(tree.peaks.len(), 2);
+        assert_eq!(
+            tree.root(),
+            NodeData::combine(
+                &NodeData::combine(&leaf(0), &leaf(1), BRANCH_ID).unwrap(),
+                &leaf(2),
+                BRANCH_ID
+            )
+        );
+
+        tree.append(leaf(3)).unwrap();
+        assert_eq!(tree.peaks.len(), 1);
+        assert_eq!(tree.len(), 4);
+
+        let root = tree.root().unwrap();
+        assert_eq!(root.start_height, 0);
+        assert_eq!(root.end_height, 3);
+    }
+
+    #[test]
+    fn root_depends_on_branch_id() {
+        let mut a = Tree::new(BRANCH_ID);
+        let mut b = Tree::new(BRANCH_ID + 1);
+        for i in 0..3 {
+            a.append(leaf(i)).unwrap();
+            b.append(leaf(i)).unwrap();
+        }
+        assert_ne!(a.root_hash(), b.root_hash());
+    }
+
+    #[test]
+    fn inclusion_proofs() {
+        let mut tree = Tree::new(BRANCH_ID);
+        for i in 0..23 {
+            tree.append(leaf(i)).unwrap();
+
+            let root_hash = tree.root_hash().unwrap();
+            for j in 0..(i + 1) {
+                let proof = tree.inclusion_proof(j).unwrap();
+                assert!(proof.verify(&leaf(j), &root_hash, BRANCH_ID));
+                assert!(!proof.verify(&leaf(j + 100), &root_hash, BRANCH_ID));
+                assert!(!proof.verify(&leaf(j), &root_hash, BRANCH_ID + 1));
+            }
+            assert!(tree.inclusion_proof(i + 1).is_none());
+        }
+    }
+}
@@ -0,0 +1,300 @@
+//! 256-bit arithmetic for proof-of-work targets and chain work.
+
+use std::ops::{Add, Div, Mul, Not, Shl, Shr, Sub};
+
+/// An unsigned 256-bit integer, stored as four 64-bit limbs with the most
+/// significant limb first (so that the derived ordering is numeric).
+///
+/// Arithmetic wraps on overflow, matching `arith_uint256` in zcashd.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct U256([u64; 4]);
+
+impl U256 {
+    pub fn zero() -> Self {
+        U256([0; 4])
+    }
+
+    pub fn from_u64(value: u64) -> Self {
+        U256([0, 0, 0, value])
+    }
+
+    /// Reads a little-endian 256-bit integer, such as a block hash.
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0; 4];
+        for (i, b) in bytes.iter().enumerate() {
+            limbs[3 - i / 8] |= (*b as u64) << (8 * (i % 8));
+        }
+        U256(limbs)
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = (self.0[3 - i / 8] >> (8 * (i % 8))) as u8;
+        }
+        bytes
+    }
+
+    fn low_u64(&self) -> u64 {
+        self.0[3]
+    }
+
+    /// Returns the number of significant bits.
+    fn bits(&self) -> usize {
+        for (i, limb) in self.0.iter().enumerate() {
+            if *limb != 0 {
+                return 64 * (4 - i) - limb.leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
+    /// Decodes a target from the compact form used in block headers
+    /// (`nBits`), returning `None` if it is zero, negative or overflows.
+    pub fn from_compact(compact: u32) -> Option<Self> {
+        let size = (compact >> 24) as usize;
+        let mut word = compact & 0x007f_ffff;
+        let negative = compact & 0x0080_0000 != 0;
+
+        let value = if size <= 3 {
+            word >>= 8 * (3 - size);
+            U256::from_u64(word as u64)
+        } else if (size > 34) || (word > 0xff && size > 33) || (word > 0xffff && size > 32) {
+            // Overflow
+            return None;
+        } else {
+            U256::from_u64(word as u64) << (8 * (size - 3))
+        };
+
+        if word == 0 || negative {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Encodes this value in compact form, discarding all but the three
+    /// most significant bytes.
+    pub fn to_compact(&self) -> u32 {
+        let mut size = (self.bits() + 7) / 8;
+        let mut compact = if size <= 3 {
+            (self.low_u64() << (8 * (3 - size))) as u32
+        } else {
+            (*self >> (8 * (size - 3))).low_u64() as u32
+        };
+
+        // The 0x00800000 bit denotes the sign, so if it is already set, divide
+        // the mantissa by 256 and increase the exponent.
+        if compact & 0x0080_0000 != 0 {
+            compact >>= 8;
+            size += 1;
+        }
+        compact | (size as u32) << 24
+    }
+
+    /// Returns the expected number of hashes needed to meet this target, which
+    /// is `2^256 / (target + 1)`.
+    pub fn work_for_target(&self) -> Self {
+        // 2^256 doesn't fit, so compute (2^256 - target - 1) / (target + 1) + 1
+        let one = U256::from_u64(1);
+        let target_plus_one = *self + one;
+        if target_plus_one == U256::zero() {
+            return one;
+        }
+        (!*self) / target_plus_one + one
+    }
+}
+
+impl Not for U256 {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        let mut result = self.0;
+        for limb in result.iter_mut() {
+            *limb = !*limb;
+        }
+        U256(result)
+    }
+}
+
+impl Add for U256 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let mut result = [0; 4];
+        let mut carry = false;
+        for i in (0..4).rev() {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            result[i] = sum;
+            carry = c1 || c2;
+        }
+        U256(result)
+    }
+}
+
+impl Sub for U256 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (!rhs + U256::from_u64(1))
+    }
+}
+
+impl Mul<u32> for U256 {
+    type Output = Self;
+
+    fn mul(self, rhs: u32) -> Self {
+        let rhs = rhs as u64;
+        let mut result = [0; 4];
+        let mut carry = 0;
+        for i in (0..4).rev() {
+            // Multiply each 32-bit half of the limb separately so that the
+            // intermediate products fit in a u64
+            let lo = (self.0[i] & 0xffff_ffff) * rhs + carry;
+            let hi = (self.0[i] >> 32) * rhs + (lo >> 32);
+            result[i] = (hi << 32) | (lo & 0xffff_ffff);
+            carry = hi >> 32;
+        }
+        U256(result)
+    }
+}
+
+impl Div for U256 {
+    type Output = Self;
+
+    /// Long division. Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs != U256::zero(), "division by zero");
+
+        let mut quotient = U256::zero();
+        let mut remainder = U256::zero();
+        for i in (0..self.bits()).rev() {
+            remainder = remainder << 1;
+            remainder.0[3] |= (self >> i).low_u64() & 1;
+            if remainder >= rhs {
+                remainder = remainder - rhs;
+                quotient.0[3 - i / 64] |= 1 << (i % 64);
+            }
+        }
+        quotient
+    }
+}
+
+impl Div<u64> for U256 {
+    type Output = Self;
+
+    fn div(self, rhs: u64) -> Self {
+        self / U256::from_u64(rhs)
+    }
+}
+
+impl Shl<usize> for U256 {
+    type Output = Self;
+
+    fn shl(self, shift: usize) -> Self {
+        let mut result = [0; 4];
+        let (limbs, bits) = (shift / 64, shift % 64);
+        for i in 0..4 {
+            if i + limbs < 4 {
+                result[i] |= self.0[i + limbs] << bits;
+                if bits > 0 && i + limbs + 1 < 4 {
+                    result[i] |= self.0[i + limbs + 1] >> (64 - bits);
+                }
+            }
+        }
+        U256(result)
+    }
+}
+
+impl Shr<usize> for U256 {
+    type Output = Self;
+
+    fn shr(self, shift: usize) -> Self {
+        let mut result = [0; 4];
+        let (limbs, bits) = (shift / 64, shift % 64);
+        for i in 0..4 {
+            if i >= limbs {
+                result[i] |= self.0[i - limbs] >> bits;
+                if bits > 0 && i > limbs {
+                    result[i] |= self.0[i - limbs - 1] << (64 - bits);
+                }
+            }
+        }
+        U256(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::U256;
+
+    fn from_be_hex(hex: &str) -> U256 {
+        let mut bytes = [0; 32];
+        for i in 0..32 {
+            bytes[31 - i] = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+        }
+        U256::from_le_bytes(&bytes)
+    }
+
+    #[test]
+    fn byte_round_trip() {
+        let mut bytes = [0; 32];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let value = U256::from_le_bytes(&bytes);
+        assert_eq!(value.low_u64(), 0x0706_0504_0302_0100);
+        assert_eq!(value.to_le_bytes(), bytes);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = from_be_hex("00000000000000000000000000000001ffffffffffffffffffffffffffffffff");
+        let one = U256::from_u64(1);
+        assert_eq!(
+            a + one,
+            from_be_hex("0000000000000000000000000000000200000000000000000000000000000000")
+        );
+        assert_eq!(a + one - one, a);
+        assert_eq!(U256::zero() - one, !U256::zero());
+        assert_eq!(a * 2 / 2, a);
+        assert_eq!(a * 0xffff_ffff, (a << 32) - a);
+        assert_eq!(a / a, one);
+        assert_eq!(a / (a + one), U256::zero());
+        assert_eq!((a << 100) >> 100, a);
+        assert_eq!(a >> 128, one);
+        assert_eq!(a.bits(), 129);
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let regtest_limit =
+            from_be_hex("0f0f0f0000000000000000000000000000000000000000000000000000000000");
+        assert_eq!(U256::from_compact(0x200f_0f0f), Some(regtest_limit));
+        assert_eq!(regtest_limit.to_compact(), 0x200f_0f0f);
+
+        assert_eq!(U256::from_compact(0x0112_3456), Some(U256::from_u64(0x12)));
+        assert_eq!(U256::from_u64(0x12).to_compact(), 0x0112_0000);
+        assert_eq!(U256::from_u64(0x80).to_compact(), 0x0200_8000);
+
+        let mainnet_limit =
+            from_be_hex("0007ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        assert_eq!(mainnet_limit.to_compact(), 0x1f07_ffff);
+
+        // Zero, negative and overflowing targets
+        assert_eq!(U256::from_compact(0x0100_3456), None);
+        assert_eq!(U256::from_compact(0x0480_0012), None);
+        assert_eq!(U256::from_compact(0xff12_3456), None);
+    }
+
+    #[test]
+    fn work() {
+        // A target of 2^255 - 1 needs two hashes on average
+        let target =
+            from_be_hex("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        assert_eq!(target.work_for_target(), U256::from_u64(2));
+        assert_eq!((target >> 8).work_for_target(), U256::from_u64(512));
+        assert_eq!((!U256::zero()).work_for_target(), U256::from_u64(1));
+    }
+}
@@ -0,0 +1,43 @@
+//! Helpers for the Bitcoin-derived serialization formats used by Zcash.
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{self, Read, Write};
+
+/// Reads a Bitcoin-style CompactSize integer, rejecting non-canonical encodings.
+pub(crate) fn read_compact<R: Read>(mut reader: R) -> io::Result<u64> {
+    let flag = reader.read_u8()?;
+    let (value, min) = match flag {
+        0xfd => (reader.read_u16::<LittleEndian>()? as u64, 0xfd),
+        0xfe => (reader.read_u32::<LittleEndian>()? as u64, 0x10000),
+        0xff => (reader.read_u64::<LittleEndian>()?, 0x100000000),
+        v => (v as u64, 0),
+    };
+
+    if value < min {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "non-canonical compact integer",
+        ))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Writes a Bitcoin-style CompactSize integer in its canonical encoding.
+pub(crate) fn write_compact<W: Write>(mut writer: W, value: u64) -> io::Result<()> {
+    match value {
+        v if v < 0xfd => writer.write_u8(v as u8),
+        v if v <= 0xffff => {
+            writer.write_u8(0xfd)?;
+            writer.write_u16::<LittleEndian>(v as u16)
+        }
+        v if v <= 0xffffffff => {
+            writer.write_u8(0xfe)?;
+            writer.write_u32::<LittleEndian>(v as u32)
+        }
+        v => {
+            writer.write_u8(0xff)?;
+            writer.write_u64::<LittleEndian>(v)
+        }
+    }
+}
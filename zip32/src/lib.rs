@@ -14,10 +14,13 @@ use fpe::ff1::{BinaryNumeralString, FF1};
 use pairing::{bls12_381::Bls12, Field, PrimeField, PrimeFieldRepr};
 use sapling_crypto::{
     jubjub::{
-        edwards, FixedGenerators, JubjubBls12, JubjubEngine, JubjubParams, ToUniform, Unknown,
+        edwards, FixedGenerators, JubjubBls12, JubjubEngine, JubjubParams, PrimeOrder, ToUniform,
+        Unknown,
     },
+    musig::MusigError,
     primitives::{Diversifier, PaymentAddress, ViewingKey},
 };
+use std::convert::TryFrom;
 use std::io::{self, Read, Write};
 
 lazy_static! {
@@ -159,6 +162,33 @@ impl<E: JubjubEngine> FullViewingKey<E> {
         }
     }
 
+    /// Returns the full viewing key for the MuSig joint key of this key's
+    /// `ak` and a co-signer's `ak_2`, keeping this key's `nk` and `ovk`.
+    ///
+    /// It is encoded like any other full viewing key, so it can be exported
+    /// with `write` to an auditor or a watch-only wallet.
+    pub fn make_multisig_with(
+        &self,
+        ak_2: edwards::Point<E, PrimeOrder>,
+        params: &E::Params,
+    ) -> Result<Self, MusigError> {
+        Ok(FullViewingKey {
+            vk: self.vk.make_multisig_with(ak_2, params)?,
+            ovk: self.ovk,
+        })
+    }
+
+    /// Returns the viewing key `(ak, nk)`, from which the incoming viewing
+    /// key and payment addresses are derived.
+    pub fn vk(&self) -> &ViewingKey<E> {
+        &self.vk
+    }
+
+    /// Returns the outgoing viewing key.
+    pub fn ovk(&self) -> [u8; 32] {
+        self.ovk.0
+    }
+
     pub fn read<R: Read>(mut reader: R, params: &E::Params) -> io::Result<Self> {
         let ak = edwards::Point::<E, Unknown>::read(&mut reader, params)?;
         let ak = match ak.as_prime_order(params) {
@@ -273,7 +303,7 @@ struct ChainCode([u8; 32]);
 pub struct DiversifierIndex(pub [u8; 11]);
 
 impl DiversifierIndex {
-    fn new() -> Self {
+    pub fn new() -> Self {
         DiversifierIndex([0; 11])
     }
 
@@ -290,6 +320,72 @@ impl DiversifierIndex {
     }
 }
 
+impl From<u32> for DiversifierIndex {
+    fn from(j: u32) -> Self {
+        DiversifierIndex::from(j as u64)
+    }
+}
+
+impl From<u64> for DiversifierIndex {
+    fn from(j: u64) -> Self {
+        let mut index = [0; 11];
+        LittleEndian::write_u64(&mut index[..8], j);
+        DiversifierIndex(index)
+    }
+}
+
+/// Returns an error if `j` does not fit in 88 bits.
+impl TryFrom<u128> for DiversifierIndex {
+    type Error = ();
+
+    fn try_from(j: u128) -> Result<Self, ()> {
+        if j >> 88 != 0 {
+            return Err(());
+        }
+
+        let mut index = [0; 11];
+        index.copy_from_slice(&j.to_le_bytes()[..11]);
+        Ok(DiversifierIndex(index))
+    }
+}
+
+impl From<DiversifierIndex> for u128 {
+    fn from(j: DiversifierIndex) -> u128 {
+        let mut bytes = [0; 16];
+        bytes[..11].copy_from_slice(&j.0);
+        u128::from_le_bytes(bytes)
+    }
+}
+
+/// An iterator over the valid diversifiers of a key, in index order, along
+/// with their indices.
+pub struct DiversifierIter {
+    dk: DiversifierKey,
+    next: Option<DiversifierIndex>,
+}
+
+impl Iterator for DiversifierIter {
+    type Item = (DiversifierIndex, Diversifier);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (j, d_j) = match self.dk.diversifier(self.next?) {
+            Ok(ret) => ret,
+            Err(()) => {
+                self.next = None;
+                return None;
+            }
+        };
+
+        let mut next = j;
+        self.next = match next.increment() {
+            Ok(()) => Some(next),
+            Err(()) => None,
+        };
+
+        Some((j, d_j))
+    }
+}
+
 /// A key used to derive diversifiers for a particular child key
 #[derive(Clone, Copy, Debug, PartialEq)]
 struct DiversifierKey([u8; 32]);
@@ -588,6 +684,15 @@ impl ExtendedFullViewingKey {
     pub fn default_address(&self) -> Result<(DiversifierIndex, PaymentAddress<Bls12>), ()> {
         self.address(DiversifierIndex::new())
     }
+
+    /// Returns an iterator over the valid diversifiers of this key, starting
+    /// from index `j`.
+    pub fn diversifiers(&self, j: DiversifierIndex) -> DiversifierIter {
+        DiversifierIter {
+            dk: self.dk,
+            next: Some(j),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -684,6 +789,65 @@ mod tests {
         assert_eq!(d_j.0, d_3);
     }
 
+    #[test]
+    fn diversifier_index_from_integer() {
+        assert_eq!(DiversifierIndex::from(0u32), DiversifierIndex::new());
+        assert_eq!(
+            DiversifierIndex::from(0x0102_0304u32),
+            DiversifierIndex([4, 3, 2, 1, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            DiversifierIndex::from(u64::max_value()),
+            DiversifierIndex([0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0])
+        );
+
+        let max = (1u128 << 88) - 1;
+        assert_eq!(
+            DiversifierIndex::try_from(max),
+            Ok(DiversifierIndex([0xff; 11]))
+        );
+        assert_eq!(
+            DiversifierIndex::try_from(0x0102_0304_0506_0708_090a_0bu128),
+            Ok(DiversifierIndex([
+                0x0b, 0x0a, 0x09, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01
+            ]))
+        );
+        assert!(DiversifierIndex::try_from(1u128 << 88).is_err());
+        assert!(DiversifierIndex::try_from(u128::max_value()).is_err());
+
+        assert_eq!(u128::from(DiversifierIndex([0xff; 11])), max);
+        assert_eq!(u128::from(DiversifierIndex::from(7u32)), 7);
+    }
+
+    #[test]
+    fn diversifier_iter() {
+        let dk = DiversifierKey([0; 32]);
+        let mut iter = DiversifierIter {
+            dk,
+            next: Some(DiversifierIndex::new()),
+        };
+
+        // Invalid diversifiers at j = 1 and j = 2 are skipped
+        let (j, d_j) = iter.next().unwrap();
+        assert_eq!(j, DiversifierIndex::from(0u32));
+        assert_eq!(d_j.0, [220, 231, 126, 188, 236, 10, 38, 175, 214, 153, 140]);
+        let (j, d_j) = iter.next().unwrap();
+        assert_eq!(j, DiversifierIndex::from(3u32));
+        assert_eq!(d_j.0, [60, 253, 170, 8, 171, 147, 220, 31, 3, 144, 34]);
+
+        // Every diversifier yielded is valid
+        for (_, d_j) in iter.take(10) {
+            assert!(d_j.g_d::<Bls12>(&JUBJUB).is_some());
+        }
+
+        // The iterator ends when the index space is exhausted
+        let mut iter = DiversifierIter {
+            dk,
+            next: Some(DiversifierIndex([0xff; 11])),
+        };
+        assert!(iter.nth(1).is_none());
+    }
+
     #[test]
     fn default_address() {
         let seed = [0; 32];
@@ -714,6 +878,45 @@ mod tests {
         assert_eq!(fvk2, fvk);
     }
 
+    #[test]
+    fn multisig_fvk() {
+        let xfvk_1 = ExtendedFullViewingKey::from(&ExtendedSpendingKey::master(&[1; 32]));
+        let xfvk_2 = ExtendedFullViewingKey::from(&ExtendedSpendingKey::master(&[2; 32]));
+        let ak_2 = xfvk_2.fvk.vk().ak.clone();
+
+        let joint = xfvk_1
+            .fvk
+            .make_multisig_with(ak_2.clone(), &JUBJUB)
+            .unwrap();
+        assert!(joint.vk().ak != xfvk_1.fvk.vk().ak);
+        assert!(joint.vk().nk == xfvk_1.fvk.vk().nk);
+        assert_eq!(joint.ovk(), xfvk_1.fvk.ovk());
+
+        // The exported key gives the multisig addresses
+        let mut ser = vec![];
+        joint.write(&mut ser).unwrap();
+        assert_eq!(ser.len(), 96);
+        let joint = FullViewingKey::<Bls12>::read(&ser[..], &JUBJUB).unwrap();
+
+        let (_, d) = xfvk_1.default_address().unwrap();
+        let addr = joint.vk().into_payment_address(d.diversifier, &JUBJUB).unwrap();
+        let expected = xfvk_1
+            .fvk
+            .vk()
+            .make_multisig_address_with(ak_2, d.diversifier, &JUBJUB)
+            .unwrap()
+            .unwrap();
+        assert!(addr.pk_d == expected.pk_d);
+
+        assert_eq!(
+            xfvk_1
+                .fvk
+                .make_multisig_with(xfvk_1.fvk.vk().ak.clone(), &JUBJUB)
+                .err(),
+            Some(MusigError::DuplicateKey)
+        );
+    }
+
     #[test]
     fn test_vectors() {
         struct TestVector {